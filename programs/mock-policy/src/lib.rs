@@ -0,0 +1,53 @@
+use anchor_lang::prelude::*;
+use multisig_vault::state::{PolicyCheck, PolicyStage, Proposal, ProposalStatus};
+
+declare_id!("7dGHgW1W8QqnzA5f9h8xswZkzvpxDxXXdCQ2y2oQrSSr");
+
+/// Largest amount the mock policy lets through.
+pub const MAX_AMOUNT: u64 = 500_000_000;
+
+/// Stand-in policy program for testing the vault's execution hooks.
+#[program]
+pub mod mock_policy {
+    use super::*;
+
+    /// Rejects executions moving more than `MAX_AMOUNT`. After execution it
+    /// also checks that the vault wrote the proposal back before the call.
+    pub fn check(ctx: Context<Check>, args: PolicyCheck) -> Result<()> {
+        require!(args.amount <= MAX_AMOUNT, MockPolicyError::AmountTooLarge);
+
+        if args.stage == PolicyStage::PostExecution && args.proposal != Pubkey::default() {
+            let info = &ctx.accounts.proposal;
+            require_keys_eq!(
+                *info.owner,
+                multisig_vault::ID,
+                MockPolicyError::ProposalNotWrittenBack
+            );
+            let proposal = Proposal::try_deserialize(&mut &info.try_borrow_data()?[..])?;
+            require!(
+                proposal.status != ProposalStatus::Approved,
+                MockPolicyError::ProposalNotWrittenBack
+            );
+        }
+
+        Ok(())
+    }
+}
+
+#[derive(Accounts)]
+pub struct Check<'info> {
+    /// CHECK: vault config, read-only
+    pub vault: UncheckedAccount<'info>,
+    /// CHECK: proposal being executed, or the vault config for instant redemptions
+    pub proposal: UncheckedAccount<'info>,
+    /// CHECK: account receiving the assets
+    pub destination: UncheckedAccount<'info>,
+}
+
+#[error_code]
+pub enum MockPolicyError {
+    #[msg("Amount exceeds the mock policy limit")]
+    AmountTooLarge,
+    #[msg("Proposal state was not written back before the post-execution check")]
+    ProposalNotWrittenBack,
+}
@@ -0,0 +1,128 @@
+//! Off-chain helpers for reading vault accounts.
+
+use crate::state::{OffchainMemo, Proposal, VaultConfig};
+use anchor_lang::prelude::*;
+use std::path::Path;
+
+/// Signers whose bit is set in a proposal vote bitmap.
+pub fn decode_vote_bitmap(bitmap: u64, signers: &[Pubkey]) -> Vec<Pubkey> {
+    signers
+        .iter()
+        .enumerate()
+        .filter(|(index, _)| bitmap & (1u64 << index) != 0)
+        .map(|(_, signer)| *signer)
+        .collect()
+}
+
+/// Signers grouped by how they voted on a proposal.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ProposalVotes {
+    pub approvals: Vec<Pubkey>,
+    pub rejections: Vec<Pubkey>,
+    pub abstentions: Vec<Pubkey>,
+    /// Cancel votes cast under the current signer set.
+    pub cancellations: Vec<Pubkey>,
+}
+
+/// Votes cast on `proposal`, or `None` if the vault's signer set has changed
+/// since the proposal was created.
+pub fn proposal_votes(proposal: &Proposal, vault: &VaultConfig) -> Option<ProposalVotes> {
+    if proposal.config_epoch != vault.config_epoch {
+        return None;
+    }
+    Some(ProposalVotes {
+        approvals: decode_vote_bitmap(proposal.approval_bitmap, &vault.signers),
+        rejections: decode_vote_bitmap(proposal.rejection_bitmap, &vault.signers),
+        abstentions: decode_vote_bitmap(proposal.abstain_bitmap, &vault.signers),
+        cancellations: cancel_votes(proposal, vault),
+    })
+}
+
+/// Cancel votes that still count. The cancel bitmap has its own epoch and is
+/// reset on the next cancel vote after the signer set changes, so votes from
+/// an older epoch are dropped.
+pub fn cancel_votes(proposal: &Proposal, vault: &VaultConfig) -> Vec<Pubkey> {
+    if proposal.cancel_epoch != vault.config_epoch {
+        return Vec::new();
+    }
+    decode_vote_bitmap(proposal.cancel_bitmap, &vault.signers)
+}
+
+/// blake3 hash to store in an `OffchainMemo` for `content`.
+pub fn memo_hash(content: &[u8]) -> [u8; 32] {
+    *blake3::hash(content).as_bytes()
+}
+
+/// Whether `content` matches the hash recorded on-chain.
+pub fn verify_memo(memo: &OffchainMemo, content: &[u8]) -> bool {
+    memo_hash(content) == memo.hash
+}
+
+/// Reads a local copy of the memo document and checks it against the
+/// on-chain hash.
+pub fn verify_memo_file(memo: &OffchainMemo, path: impl AsRef<Path>) -> std::io::Result<bool> {
+    let content = std::fs::read(path)?;
+    Ok(verify_memo(memo, &content))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn decode_vote_bitmap_maps_bits_to_signers() {
+        let signers: Vec<Pubkey> = (0..4).map(|_| Pubkey::new_unique()).collect();
+
+        assert!(decode_vote_bitmap(0, &signers).is_empty());
+        assert_eq!(
+            decode_vote_bitmap(0b1010, &signers),
+            vec![signers[1], signers[3]]
+        );
+    }
+
+    #[test]
+    fn decode_vote_bitmap_ignores_bits_past_the_signer_list() {
+        let signers: Vec<Pubkey> = (0..2).map(|_| Pubkey::new_unique()).collect();
+
+        assert_eq!(
+            decode_vote_bitmap(0b101 | 1 << 63, &signers),
+            vec![signers[0]]
+        );
+    }
+
+    fn memo_for(content: &[u8]) -> OffchainMemo {
+        OffchainMemo {
+            hash: memo_hash(content),
+            uri: "ipfs://invoice".to_string(),
+        }
+    }
+
+    #[test]
+    fn memo_hash_is_blake3() {
+        assert_eq!(
+            memo_hash(b"invoice #42"),
+            *blake3::hash(b"invoice #42").as_bytes()
+        );
+        assert_ne!(memo_hash(b"invoice #42"), memo_hash(b"invoice #43"));
+    }
+
+    #[test]
+    fn verify_memo_detects_changed_content() {
+        let memo = memo_for(b"pay 100 USDC");
+
+        assert!(verify_memo(&memo, b"pay 100 USDC"));
+        assert!(!verify_memo(&memo, b"pay 1000 USDC"));
+    }
+
+    #[test]
+    fn verify_memo_file_reads_and_checks_content() {
+        let path = std::env::temp_dir().join(format!("memo-{}.txt", Pubkey::new_unique()));
+        std::fs::write(&path, b"pay 100 USDC").unwrap();
+
+        assert!(verify_memo_file(&memo_for(b"pay 100 USDC"), &path).unwrap());
+        assert!(!verify_memo_file(&memo_for(b"pay 1000 USDC"), &path).unwrap());
+
+        std::fs::remove_file(&path).unwrap();
+        assert!(verify_memo_file(&memo_for(b"pay 100 USDC"), &path).is_err());
+    }
+}
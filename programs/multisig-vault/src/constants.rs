@@ -2,8 +2,43 @@ use anchor_lang::prelude::*;
 
 #[constant]
 pub const SEED: &str = "anchor";
+
+/// Current account layout versions; see `migrate_vault` and `migrate_proposal`.
+pub const VAULT_VERSION: u8 = 1;
+pub const PROPOSAL_VERSION: u8 = 1;
+
 pub const VAULT_SEED: &[u8] = b"vault";
 pub const PROPOSAL_SEED: &[u8] = b"proposal";
 pub const TREASURY_SEED: &[u8] = b"treasury";
-pub const MAX_SIGNERS: usize = 10;
+pub const STREAM_SEED: &[u8] = b"stream";
+pub const STREAM_RESERVE_SEED: &[u8] = b"stream_reserve";
+pub const RECOVERY_SEED: &[u8] = b"recovery";
+pub const STAKE_SEED: &[u8] = b"stake";
+pub const DEPOSIT_SEED: &[u8] = b"deposit";
+pub const SHARE_MINT_SEED: &[u8] = b"share_mint";
+pub const ASSET_REGISTRY_SEED: &[u8] = b"assets";
+pub const MAX_SIGNERS: usize = 64;
 pub const MAX_DESCRIPTION_LEN: usize = 200;
+pub const MAX_MEMO_URI_LEN: usize = 96;
+pub const MAX_ADDRESS_BOOK: usize = 20;
+pub const MAX_THRESHOLD_TIERS: usize = 8;
+pub const MAX_VETO_COUNCIL: usize = 5;
+pub const MAX_TIMELOCK_SECS: i64 = 365 * 24 * 60 * 60;
+pub const MAX_VOTING_PERIOD_SECS: i64 = 365 * 24 * 60 * 60;
+pub const MAX_RECOVERY_KEYS: usize = 5;
+#[cfg(not(feature = "short-recovery-delay"))]
+pub const MIN_RECOVERY_DELAY_SECS: i64 = 7 * 24 * 60 * 60;
+/// Test builds only, so the TS suite can execute a recovery.
+#[cfg(feature = "short-recovery-delay")]
+pub const MIN_RECOVERY_DELAY_SECS: i64 = 2;
+pub const SHARE_DECIMALS: u8 = 9;
+pub const REDEMPTION_WINDOW_SECS: i64 = 24 * 60 * 60;
+pub const MAX_ALLOWED_MINTS: usize = 20;
+pub const MAX_ALLOWED_DEPOSITORS: usize = 20;
+pub const MAX_TRACKED_ASSETS: usize = 32;
+pub const SOL_DECIMALS: u8 = 9;
+/// Treasury valuations are reported in micro-USD.
+pub const USD_DECIMALS: u8 = 6;
+
+/// Asset key used for native SOL in per-asset accounts such as `DepositRecord`.
+pub const SOL_ASSET: Pubkey = Pubkey::new_from_array([0; 32]);
@@ -1,5 +1,6 @@
 use anchor_lang::prelude::*;
 
+// New variants go at the end: codes are positional and clients match on them.
 #[error_code]
 pub enum VaultError {
     #[msg("Number of signers exceeds maximum")]
@@ -16,7 +17,7 @@ pub enum VaultError {
     ThresholdNotMet,
     #[msg("Proposal has already been executed")]
     AlreadyExecuted,
-    #[msg("Only the proposer can cancel")]
+    #[msg("Only the proposer can cancel or amend")]
     NotProposer,
     #[msg("Description too long")]
     DescriptionTooLong,
@@ -30,4 +31,153 @@ pub enum VaultError {
     PriceConditionNotMet,
     #[msg("Price feed is stale")]
     StalePriceFeed,
+
+    // STREAM ERROR CODES
+    #[msg("Proposal transfer type does not match this instruction")]
+    InvalidTransferType,
+    #[msg("Stream schedule must satisfy start <= cliff <= end and start < end")]
+    InvalidStreamSchedule,
+    #[msg("Nothing vested to withdraw")]
+    NothingToWithdraw,
+    #[msg("Stream has been cancelled")]
+    StreamCancelled,
+
+    // GOVERNANCE ERROR CODES
+    #[msg("Recipient is not in the vault address book")]
+    RecipientNotAllowed,
+    #[msg("Address book is full")]
+    AddressBookFull,
+    #[msg("Recipient is already in the address book")]
+    RecipientAlreadyListed,
+    #[msg("Recipient is not in the address book")]
+    RecipientNotListed,
+    #[msg("Too many threshold tiers")]
+    TooManyThresholdTiers,
+    #[msg("Threshold tier not found")]
+    ThresholdTierNotFound,
+
+    #[msg("Vault is paused")]
+    VaultPaused,
+    #[msg("Only the guardian can pause the vault")]
+    NotGuardian,
+
+    #[msg("Proposal has been vetoed")]
+    ProposalVetoed,
+    #[msg("Proposal time lock has not elapsed")]
+    TimelockActive,
+    #[msg("Veto window has closed")]
+    VetoWindowClosed,
+    #[msg("Signer is not on the veto council")]
+    NotVetoMember,
+    #[msg("Veto council is full")]
+    VetoCouncilFull,
+    #[msg("Key is already on the veto council")]
+    VetoMemberAlreadyListed,
+    #[msg("Key is not on the veto council")]
+    VetoMemberNotListed,
+    #[msg("Time lock must be between 0 and MAX_TIMELOCK_SECS")]
+    InvalidTimelock,
+
+    // RECOVERY ERROR CODES
+    #[msg("Vault has no recovery configuration")]
+    RecoveryNotConfigured,
+    #[msg("Signer is not a recovery key")]
+    NotRecoveryKey,
+    #[msg("Number of recovery keys exceeds maximum")]
+    TooManyRecoveryKeys,
+    #[msg("Recovery delay is shorter than the minimum")]
+    RecoveryDelayTooShort,
+    #[msg("Recovery has not reached its threshold")]
+    RecoveryNotApproved,
+    #[msg("Recovery waiting period has not elapsed")]
+    RecoveryDelayActive,
+
+    #[msg("Provide exactly one of recipient_ata or recipient_token_account")]
+    InvalidRecipientTokenAccount,
+
+    // STAKE ERROR CODES
+    #[msg("Stake account does not match the proposal")]
+    InvalidStakeAccount,
+    #[msg("Vote account does not match the proposal")]
+    InvalidVoteAccount,
+
+    // SHARE POOL ERROR CODES
+    #[msg("Vault has no share pool")]
+    SharePoolNotInitialized,
+    #[msg("Redemption exceeds the auto-approval limit; use request_redemption")]
+    RedemptionNeedsApproval,
+    #[msg("Amount is too small to mint or redeem any shares")]
+    ZeroShares,
+
+    // DEPOSIT POLICY ERROR CODES
+    #[msg("Mint is not accepted for deposits")]
+    MintNotAllowed,
+    #[msg("Depositor is not on the deposit allowlist")]
+    DepositorNotAllowed,
+    #[msg("Deposit allowlist is full")]
+    DepositAllowlistFull,
+    #[msg("Key is already on the deposit allowlist")]
+    AlreadyOnDepositAllowlist,
+    #[msg("Key is not on the deposit allowlist")]
+    NotOnDepositAllowlist,
+
+    // ASSET REGISTRY ERROR CODES
+    #[msg("Asset registry is full")]
+    AssetRegistryFull,
+    #[msg("Mint is not in the asset registry")]
+    AssetNotTracked,
+    #[msg("Asset registry account is required for this action")]
+    AssetRegistryRequired,
+
+    // VALUATION ERROR CODES
+    #[msg("No price feed is configured for this asset")]
+    MissingPriceFeed,
+    #[msg("Valuation accounts must be vault token account and price update pairs")]
+    InvalidValuationAccounts,
+    #[msg("Price feed returned a negative price")]
+    InvalidPrice,
+    #[msg("Treasury value overflowed")]
+    ValuationOverflow,
+
+    // MIGRATION ERROR CODES
+    #[msg("Account uses an old layout; run the migration instruction first")]
+    AccountNotMigrated,
+    #[msg("Account is already on the current layout")]
+    AlreadyMigrated,
+    #[msg("Account is not a legacy account of the expected type")]
+    InvalidLegacyAccount,
+
+    // SIGNER SET ERROR CODES
+    #[msg("Key is not a signer of this vault")]
+    SignerNotFound,
+    #[msg("Signer set changed since this proposal was created")]
+    StaleProposal,
+
+    #[msg("Memo URI exceeds maximum length")]
+    MemoUriTooLong,
+
+    // VOTING ERROR CODES
+    #[msg("Quorum must not exceed the number of signers, and must be set for SimpleMajority")]
+    InvalidQuorum,
+    #[msg("Voting period has ended")]
+    VotingClosed,
+    #[msg("Voting period has not ended")]
+    VotingStillOpen,
+    #[msg("Voting period must be between 0 and MAX_VOTING_PERIOD_SECS")]
+    InvalidVotingPeriod,
+    #[msg("Proposal can only be amended before other signers vote")]
+    ProposalHasVotes,
+
+    // POLICY HOOK ERROR CODES
+    #[msg("Policy program account missing or does not match the vault's")]
+    PolicyProgramMismatch,
+
+    #[msg("Arithmetic overflow")]
+    ArithmeticOverflow,
+    #[msg("Amount is not valid for this transfer type")]
+    InvalidAmount,
+    #[msg("Share pool has shares outstanding but no lamports")]
+    SharePoolInsolvent,
+    #[msg("Tokens are reserved for active streams")]
+    StreamFundsReserved,
 }
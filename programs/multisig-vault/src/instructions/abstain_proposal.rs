@@ -0,0 +1,48 @@
+use crate::constants::*;
+use crate::error::VaultError;
+use crate::state::*;
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+pub struct AbstainProposal<'info> {
+    pub signer: Signer<'info>,
+
+    #[account(
+        seeds = [VAULT_SEED, vault_config.creator.as_ref()],
+        bump = vault_config.bump,
+        constraint = vault_config.version == VAULT_VERSION @ VaultError::AccountNotMigrated,
+    )]
+    pub vault_config: Account<'info, VaultConfig>,
+
+    #[account(
+        mut,
+        seeds = [PROPOSAL_SEED, vault_config.key().as_ref(), proposal.proposal_id.to_le_bytes().as_ref()],
+        bump = proposal.bump,
+        constraint = proposal.version == PROPOSAL_VERSION @ VaultError::AccountNotMigrated,
+        constraint = proposal.vault == vault_config.key() @ VaultError::UnauthorizedSigner,
+    )]
+    pub proposal: Account<'info, Proposal>,
+}
+
+/// Records a vote that counts toward quorum but not the approval threshold.
+pub fn handler(ctx: Context<AbstainProposal>) -> Result<()> {
+    let vault = &ctx.accounts.vault_config;
+    let proposal = &mut ctx.accounts.proposal;
+    let signer_key = ctx.accounts.signer.key();
+
+    require!(
+        proposal.status == ProposalStatus::Active,
+        VaultError::ProposalNotActive
+    );
+    require!(
+        !vault.paused || matches!(proposal.transfer_type, TransferType::Governance { .. }),
+        VaultError::VaultPaused
+    );
+    proposal.require_voting_open()?;
+    let bit = proposal.voter_bit(vault, &signer_key)?;
+
+    proposal.abstain_bitmap |= bit;
+    proposal.refresh_status(vault)?;
+
+    Ok(())
+}
@@ -0,0 +1,94 @@
+use crate::constants::*;
+use crate::error::VaultError;
+use crate::state::*;
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+#[instruction(
+    recipient: Pubkey,
+    amount: u64,
+    description: String,
+    price_condition: Option<PriceCondition>,
+    memo: Option<OffchainMemo>,
+)]
+pub struct AmendProposal<'info> {
+    #[account(mut)]
+    pub proposer: Signer<'info>,
+
+    #[account(
+        seeds = [VAULT_SEED, vault_config.creator.as_ref()],
+        bump = vault_config.bump,
+        constraint = vault_config.version == VAULT_VERSION @ VaultError::AccountNotMigrated,
+    )]
+    pub vault_config: Account<'info, VaultConfig>,
+
+    #[account(
+        mut,
+        seeds = [PROPOSAL_SEED, vault_config.key().as_ref(), proposal.proposal_id.to_le_bytes().as_ref()],
+        bump = proposal.bump,
+        constraint = proposal.version == PROPOSAL_VERSION @ VaultError::AccountNotMigrated,
+        constraint = proposal.vault == vault_config.key() @ VaultError::UnauthorizedSigner,
+        constraint = proposal.proposer == proposer.key() @ VaultError::NotProposer,
+        realloc = Proposal::space(&description, memo.as_ref()),
+        realloc::payer = proposer,
+        realloc::zero = false,
+    )]
+    pub proposal: Account<'info, Proposal>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Replaces the editable fields of a proposal nobody else has voted on yet.
+/// The proposer's approval is cleared, so they must approve the new version.
+pub fn handler(
+    ctx: Context<AmendProposal>,
+    recipient: Pubkey,
+    amount: u64,
+    description: String,
+    price_condition: Option<PriceCondition>,
+    memo: Option<OffchainMemo>,
+) -> Result<()> {
+    let vault = &ctx.accounts.vault_config;
+    let proposal = &mut ctx.accounts.proposal;
+
+    require!(
+        proposal.status == ProposalStatus::Active,
+        VaultError::ProposalNotActive
+    );
+    require!(
+        !vault.paused || matches!(proposal.transfer_type, TransferType::Governance { .. }),
+        VaultError::VaultPaused
+    );
+    require!(
+        proposal.transfer_type != TransferType::RedeemShares,
+        VaultError::InvalidTransferType
+    );
+    require!(!proposal.is_stale(vault), VaultError::StaleProposal);
+    proposal.require_voting_open()?;
+
+    // Only the proposer's own approval may exist
+    let proposer_bit = vault
+        .signer_index(&proposal.proposer)
+        .map_or(0, |index| 1u64 << index);
+    require!(
+        proposal.approval_bitmap & !proposer_bit == 0
+            && proposal.rejection_bitmap == 0
+            && proposal.abstain_bitmap == 0,
+        VaultError::ProposalHasVotes
+    );
+
+    Proposal::validate_description(&description, memo.as_ref())?;
+    proposal.transfer_type.validate_amount(amount)?;
+    proposal.threshold =
+        vault.threshold_for_transfer(&proposal.transfer_type, &recipient, amount)?;
+
+    proposal.recipient = recipient;
+    proposal.amount = amount;
+    proposal.description = description;
+    proposal.memo = memo;
+    proposal.price_condition = price_condition;
+    proposal.approval_bitmap = 0;
+    proposal.amendment_count += 1;
+
+    Ok(())
+}
@@ -10,6 +10,7 @@ pub struct ApproveProposal<'info> {
     #[account(
         seeds = [VAULT_SEED, vault_config.creator.as_ref()],
         bump = vault_config.bump,
+        constraint = vault_config.version == VAULT_VERSION @ VaultError::AccountNotMigrated,
     )]
     pub vault_config: Account<'info, VaultConfig>,
 
@@ -17,6 +18,7 @@ pub struct ApproveProposal<'info> {
         mut,
         seeds = [PROPOSAL_SEED, vault_config.key().as_ref(), proposal.proposal_id.to_le_bytes().as_ref()],
         bump = proposal.bump,
+        constraint = proposal.version == PROPOSAL_VERSION @ VaultError::AccountNotMigrated,
         constraint = proposal.vault == vault_config.key() @ VaultError::UnauthorizedSigner,
     )]
     pub proposal: Account<'info, Proposal>,
@@ -32,19 +34,14 @@ pub fn handler(ctx: Context<ApproveProposal>) -> Result<()> {
         VaultError::ProposalNotActive
     );
     require!(
-        vault.signers.contains(&signer_key),
-        VaultError::UnauthorizedSigner
+        !vault.paused || matches!(proposal.transfer_type, TransferType::Governance { .. }),
+        VaultError::VaultPaused
     );
-    require!(
-        !proposal.approvals.contains(&signer_key) && !proposal.rejections.contains(&signer_key),
-        VaultError::AlreadyVoted
-    );
-
-    proposal.approvals.push(signer_key);
+    proposal.require_voting_open()?;
+    let bit = proposal.voter_bit(vault, &signer_key)?;
 
-    if proposal.approvals.len() as u8 >= vault.threshold {
-        proposal.status = ProposalStatus::Approved;
-    }
+    proposal.approval_bitmap |= bit;
+    proposal.refresh_status(vault)?;
 
     Ok(())
 }
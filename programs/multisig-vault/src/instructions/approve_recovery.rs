@@ -0,0 +1,49 @@
+use crate::constants::*;
+use crate::error::VaultError;
+use crate::state::*;
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+pub struct ApproveRecovery<'info> {
+    pub recovery_key: Signer<'info>,
+
+    #[account(
+        seeds = [VAULT_SEED, vault_config.creator.as_ref()],
+        bump = vault_config.bump,
+        constraint = vault_config.version == VAULT_VERSION @ VaultError::AccountNotMigrated,
+    )]
+    pub vault_config: Account<'info, VaultConfig>,
+
+    #[account(
+        mut,
+        seeds = [RECOVERY_SEED, vault_config.key().as_ref()],
+        bump = recovery_request.bump,
+    )]
+    pub recovery_request: Account<'info, RecoveryRequest>,
+}
+
+pub fn handler(ctx: Context<ApproveRecovery>) -> Result<()> {
+    let recovery = ctx
+        .accounts
+        .vault_config
+        .recovery
+        .as_ref()
+        .ok_or(error!(VaultError::RecoveryNotConfigured))?;
+    let request = &mut ctx.accounts.recovery_request;
+    let key = ctx.accounts.recovery_key.key();
+
+    require!(recovery.keys.contains(&key), VaultError::NotRecoveryKey);
+    require!(!request.approvals.contains(&key), VaultError::AlreadyVoted);
+
+    // Drop approvals from keys removed since they voted, which also frees
+    // their slots in the fixed-size vec.
+    request.approvals.retain(|k| recovery.keys.contains(k));
+    request.approvals.push(key);
+    request.refresh(
+        recovery.threshold,
+        recovery.delay_secs,
+        Clock::get()?.unix_timestamp,
+    )?;
+
+    Ok(())
+}
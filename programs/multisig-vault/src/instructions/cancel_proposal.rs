@@ -10,6 +10,7 @@ pub struct CancelProposal<'info> {
     #[account(
         seeds = [VAULT_SEED, vault_config.creator.as_ref()],
         bump = vault_config.bump,
+        constraint = vault_config.version == VAULT_VERSION @ VaultError::AccountNotMigrated,
     )]
     pub vault_config: Account<'info, VaultConfig>,
 
@@ -17,20 +18,26 @@ pub struct CancelProposal<'info> {
         mut,
         seeds = [PROPOSAL_SEED, vault_config.key().as_ref(), proposal.proposal_id.to_le_bytes().as_ref()],
         bump = proposal.bump,
+        constraint = proposal.version == PROPOSAL_VERSION @ VaultError::AccountNotMigrated,
         constraint = proposal.vault == vault_config.key() @ VaultError::NotProposer,
     )]
     pub proposal: Account<'info, Proposal>,
 }
 
+/// The proposer can cancel their active proposal. Once it is stale, any
+/// current signer can, since it can no longer be voted on.
 pub fn handler(ctx: Context<CancelProposal>) -> Result<()> {
+    let vault = &ctx.accounts.vault_config;
     let proposal = &mut ctx.accounts.proposal;
+    let signer = ctx.accounts.signer.key();
 
     require!(
         proposal.status == ProposalStatus::Active,
         VaultError::ProposalNotActive
     );
     require!(
-        proposal.proposer == ctx.accounts.signer.key(),
+        proposal.proposer == signer
+            || (proposal.is_stale(vault) && vault.signers.contains(&signer)),
         VaultError::NotProposer
     );
 
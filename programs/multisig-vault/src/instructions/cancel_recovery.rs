@@ -0,0 +1,63 @@
+use crate::constants::*;
+use crate::error::VaultError;
+use crate::state::*;
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+pub struct CancelRecovery<'info> {
+    pub signer: Signer<'info>,
+
+    #[account(
+        seeds = [VAULT_SEED, vault_config.creator.as_ref()],
+        bump = vault_config.bump,
+        constraint = vault_config.version == VAULT_VERSION @ VaultError::AccountNotMigrated,
+    )]
+    pub vault_config: Account<'info, VaultConfig>,
+
+    #[account(
+        mut,
+        seeds = [RECOVERY_SEED, vault_config.key().as_ref()],
+        bump = recovery_request.bump,
+        has_one = initiator,
+    )]
+    pub recovery_request: Account<'info, RecoveryRequest>,
+
+    /// CHECK: rent refund destination, validated by has_one
+    #[account(mut)]
+    pub initiator: UncheckedAccount<'info>,
+}
+
+pub fn handler(ctx: Context<CancelRecovery>) -> Result<()> {
+    let vault = &ctx.accounts.vault_config;
+    let key = ctx.accounts.signer.key();
+
+    // Any current signer can cancel a recovery, at any point before execution.
+    // Recovery keys cancel by reaching the recovery threshold, so a single
+    // rogue key can't hold the request slot when the signers are lost.
+    let cancelled = if vault.signers.contains(&key) {
+        true
+    } else {
+        let recovery = vault
+            .recovery
+            .as_ref()
+            .ok_or(error!(VaultError::UnauthorizedSigner))?;
+        require!(recovery.keys.contains(&key), VaultError::UnauthorizedSigner);
+
+        let request = &mut ctx.accounts.recovery_request;
+        require!(
+            !request.cancel_votes.contains(&key),
+            VaultError::AlreadyVoted
+        );
+        request.cancel_votes.retain(|k| recovery.keys.contains(k));
+        request.cancel_votes.push(key);
+        request.cancel_votes.len() >= recovery.threshold as usize
+    };
+
+    if cancelled {
+        ctx.accounts
+            .recovery_request
+            .close(ctx.accounts.initiator.to_account_info())?;
+    }
+
+    Ok(())
+}
@@ -0,0 +1,108 @@
+use crate::constants::*;
+use crate::error::VaultError;
+use crate::state::*;
+use anchor_lang::prelude::*;
+use anchor_spl::token::TokenAccount;
+
+#[derive(Accounts)]
+pub struct CancelStream<'info> {
+    pub executor: Signer<'info>,
+
+    #[account(
+        seeds = [VAULT_SEED, vault_config.creator.as_ref()],
+        bump = vault_config.bump,
+        constraint = vault_config.version == VAULT_VERSION @ VaultError::AccountNotMigrated,
+    )]
+    pub vault_config: Account<'info, VaultConfig>,
+
+    #[account(
+        mut,
+        seeds = [PROPOSAL_SEED, vault_config.key().as_ref(), proposal.proposal_id.to_le_bytes().as_ref()],
+        bump = proposal.bump,
+        constraint = proposal.version == PROPOSAL_VERSION @ VaultError::AccountNotMigrated,
+        constraint = proposal.vault == vault_config.key() @ VaultError::UnauthorizedSigner,
+    )]
+    pub proposal: Account<'info, Proposal>,
+
+    #[account(
+        mut,
+        seeds = [STREAM_SEED, stream.proposal.as_ref()],
+        bump = stream.bump,
+        constraint = stream.vault == vault_config.key() @ VaultError::UnauthorizedSigner,
+    )]
+    pub stream: Account<'info, Stream>,
+
+    #[account(
+        mut,
+        seeds = [STREAM_RESERVE_SEED, vault_config.key().as_ref(), stream.mint.as_ref()],
+        bump = stream_reserve.bump,
+    )]
+    pub stream_reserve: Account<'info, StreamReserve>,
+
+    #[account(
+        associated_token::mint = stream.mint,
+        associated_token::authority = vault_config,
+    )]
+    pub vault_ata: Account<'info, TokenAccount>,
+
+    /// CHECK: must match vault_config.policy_program, validated in handler
+    pub policy_program: Option<UncheckedAccount<'info>>,
+}
+
+pub fn handler(ctx: Context<CancelStream>) -> Result<()> {
+    let proposal = &mut ctx.accounts.proposal;
+    let stream = &mut ctx.accounts.stream;
+
+    proposal.require_executable()?;
+    require!(
+        ctx.accounts
+            .vault_config
+            .signers
+            .contains(&ctx.accounts.executor.key()),
+        VaultError::UnauthorizedSigner
+    );
+    require!(
+        proposal.transfer_type
+            == TransferType::CancelStream {
+                stream: stream.key()
+            },
+        VaultError::InvalidTransferType
+    );
+    require!(stream.cancelled_at.is_none(), VaultError::StreamCancelled);
+
+    // Freeze the stream at what has vested so far. The unvested remainder never
+    // left the vault ATA; releasing its reservation returns it to the vault.
+    let now = Clock::get()?.unix_timestamp;
+    let vested = stream.vested_amount(now);
+    let released = stream.total_amount - vested;
+    let balance = ctx.accounts.vault_ata.amount;
+    let stream_info = stream.to_account_info();
+    run_policy_check(
+        &ctx.accounts.vault_config,
+        proposal,
+        &stream_info,
+        ctx.accounts.policy_program.as_ref(),
+        PolicyStage::PreExecution,
+        released,
+        balance,
+    )?;
+
+    ctx.accounts.stream_reserve.release(released);
+    stream.total_amount = vested;
+    stream.end_ts = now;
+    stream.cancelled_at = Some(now);
+
+    proposal.status = ProposalStatus::Executed;
+
+    run_policy_check(
+        &ctx.accounts.vault_config,
+        proposal,
+        &stream_info,
+        ctx.accounts.policy_program.as_ref(),
+        PolicyStage::PostExecution,
+        released,
+        balance,
+    )?;
+
+    Ok(())
+}
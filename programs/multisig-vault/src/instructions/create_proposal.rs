@@ -4,6 +4,15 @@ use crate::state::*;
 use anchor_lang::prelude::*;
 
 #[derive(Accounts)]
+#[instruction(
+    recipient: Pubkey,
+    amount: u64,
+    transfer_type: TransferType,
+    description: String,
+    price_condition: Option<PriceCondition>,
+    memo: Option<OffchainMemo>,
+    allow_partial: bool,
+)]
 pub struct CreateProposal<'info> {
     #[account(mut)]
     pub proposer: Signer<'info>,
@@ -12,13 +21,14 @@ pub struct CreateProposal<'info> {
         mut,
         seeds = [VAULT_SEED, vault_config.creator.as_ref()],
         bump = vault_config.bump,
+        constraint = vault_config.version == VAULT_VERSION @ VaultError::AccountNotMigrated,
     )]
     pub vault_config: Account<'info, VaultConfig>,
 
     #[account(
         init,
         payer = proposer,
-        space = 8 + Proposal::INIT_SPACE,
+        space = Proposal::space(&description, memo.as_ref()),
         seeds = [PROPOSAL_SEED, vault_config.key().as_ref(), vault_config.proposal_count.to_le_bytes().as_ref()],
         bump,
     )]
@@ -27,6 +37,7 @@ pub struct CreateProposal<'info> {
     pub system_program: Program<'info, System>,
 }
 
+#[allow(clippy::too_many_arguments)]
 pub fn handler(
     ctx: Context<CreateProposal>,
     recipient: Pubkey,
@@ -34,36 +45,79 @@ pub fn handler(
     transfer_type: TransferType,
     description: String,
     price_condition: Option<PriceCondition>,
+    memo: Option<OffchainMemo>,
+    allow_partial: bool,
 ) -> Result<()> {
     let vault = &ctx.accounts.vault_config;
 
+    let proposer_index = vault
+        .signer_index(&ctx.accounts.proposer.key())
+        .ok_or(error!(VaultError::UnauthorizedSigner))?;
+    Proposal::validate_description(&description, memo.as_ref())?;
+
+    // Redemption proposals are opened by share holders via request_redemption.
     require!(
-        vault.signers.contains(&ctx.accounts.proposer.key()),
-        VaultError::UnauthorizedSigner
+        transfer_type != TransferType::RedeemShares,
+        VaultError::InvalidTransferType
     );
+
+    transfer_type.validate_amount(amount)?;
+
+    if let TransferType::Stream {
+        start_ts,
+        cliff_ts,
+        end_ts,
+        ..
+    } = transfer_type
+    {
+        require!(
+            start_ts <= cliff_ts && cliff_ts <= end_ts && start_ts < end_ts,
+            VaultError::InvalidStreamSchedule
+        );
+    }
+
+    // Governance proposals stay open while paused so signers can unpause.
     require!(
-        description.len() <= MAX_DESCRIPTION_LEN,
-        VaultError::DescriptionTooLong
+        !vault.paused || matches!(transfer_type, TransferType::Governance { .. }),
+        VaultError::VaultPaused
     );
 
+    let threshold = vault.threshold_for_transfer(&transfer_type, &recipient, amount)?;
+
     let proposal = &mut ctx.accounts.proposal;
     proposal.vault = vault.key();
     proposal.proposal_id = vault.proposal_count;
     proposal.proposer = ctx.accounts.proposer.key();
     proposal.recipient = recipient;
     proposal.amount = amount;
+    proposal.allow_partial = allow_partial
+        && matches!(
+            transfer_type,
+            TransferType::Sol | TransferType::SplToken { .. }
+        );
+    proposal.amount_paid = 0;
     proposal.transfer_type = transfer_type;
     proposal.description = description;
+    proposal.memo = memo;
     proposal.price_condition = price_condition;
-    proposal.approvals = vec![ctx.accounts.proposer.key()];
-    proposal.rejections = vec![];
+    proposal.approval_bitmap = 1 << proposer_index;
+    proposal.rejection_bitmap = 0;
+    proposal.abstain_bitmap = 0;
+    proposal.config_epoch = vault.config_epoch;
+    proposal.cancel_bitmap = 0;
+    proposal.cancel_epoch = vault.config_epoch;
     proposal.status = ProposalStatus::Active;
     proposal.bump = ctx.bumps.proposal;
+    proposal.version = PROPOSAL_VERSION;
+    proposal.threshold = threshold;
+    proposal.quorum = vault.quorum;
+    proposal.executable_at = 0;
+    proposal.voting_ends_at = vault.voting_deadline(Clock::get()?.unix_timestamp)?;
+    proposal.deadline_rule = vault.deadline_rule;
+    proposal.amendment_count = 0;
 
-    // Check if auto-approved (threshold == 1)
-    if vault.threshold <= 1 {
-        proposal.status = ProposalStatus::Approved;
-    }
+    // The proposer's approval may already resolve it
+    proposal.refresh_status(vault)?;
 
     ctx.accounts.vault_config.proposal_count += 1;
 
@@ -0,0 +1,106 @@
+use crate::constants::*;
+use crate::error::VaultError;
+use crate::state::*;
+use anchor_lang::prelude::*;
+use anchor_lang::system_program;
+use anchor_spl::associated_token::AssociatedToken;
+use anchor_spl::token::{mint_to, Mint, MintTo, Token, TokenAccount};
+
+#[derive(Accounts)]
+pub struct DepositForShares<'info> {
+    #[account(mut)]
+    pub depositor: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [VAULT_SEED, vault_config.creator.as_ref()],
+        bump = vault_config.bump,
+        constraint = vault_config.version == VAULT_VERSION @ VaultError::AccountNotMigrated,
+        constraint = vault_config.deposit_policy.accepts_depositor(&depositor.key()) @ VaultError::DepositorNotAllowed,
+    )]
+    pub vault_config: Account<'info, VaultConfig>,
+
+    #[account(
+        mut,
+        seeds = [SHARE_MINT_SEED, vault_config.key().as_ref()],
+        bump,
+    )]
+    pub share_mint: Account<'info, Mint>,
+
+    #[account(
+        init_if_needed,
+        payer = depositor,
+        associated_token::mint = share_mint,
+        associated_token::authority = depositor,
+    )]
+    pub depositor_share_ata: Account<'info, TokenAccount>,
+
+    #[account(
+        init_if_needed,
+        payer = depositor,
+        space = 8 + DepositRecord::INIT_SPACE,
+        seeds = [DEPOSIT_SEED, vault_config.key().as_ref(), depositor.key().as_ref(), SOL_ASSET.as_ref()],
+        bump,
+    )]
+    pub deposit_record: Account<'info, DepositRecord>,
+
+    pub token_program: Program<'info, Token>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler(ctx: Context<DepositForShares>, amount: u64) -> Result<()> {
+    let vault_config = &ctx.accounts.vault_config;
+    let pool = vault_config
+        .share_pool
+        .ok_or(error!(VaultError::SharePoolNotInitialized))?;
+
+    let shares =
+        SharePool::shares_for_deposit(amount, ctx.accounts.share_mint.supply, pool.pool_lamports)?;
+    require!(shares > 0, VaultError::ZeroShares);
+
+    system_program::transfer(
+        CpiContext::new(
+            ctx.accounts.system_program.to_account_info(),
+            system_program::Transfer {
+                from: ctx.accounts.depositor.to_account_info(),
+                to: vault_config.to_account_info(),
+            },
+        ),
+        amount,
+    )?;
+
+    // PDA signer seeds for the vault
+    let creator = vault_config.creator;
+    let bump = [vault_config.bump];
+    let signer_seeds: &[&[&[u8]]] = &[&[VAULT_SEED, creator.as_ref(), &bump]];
+
+    mint_to(
+        CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            MintTo {
+                mint: ctx.accounts.share_mint.to_account_info(),
+                to: ctx.accounts.depositor_share_ata.to_account_info(),
+                authority: vault_config.to_account_info(),
+            },
+            signer_seeds,
+        ),
+        shares,
+    )?;
+
+    ctx.accounts.deposit_record.record(
+        vault_config.key(),
+        ctx.accounts.depositor.key(),
+        SOL_ASSET,
+        amount,
+        ctx.bumps.deposit_record,
+    )?;
+
+    let pool = ctx.accounts.vault_config.share_pool.as_mut().unwrap();
+    pool.pool_lamports = pool
+        .pool_lamports
+        .checked_add(amount)
+        .ok_or(error!(VaultError::ArithmeticOverflow))?;
+
+    Ok(())
+}
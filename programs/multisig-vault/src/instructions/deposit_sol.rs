@@ -1,5 +1,6 @@
 use crate::constants::*;
-use crate::state::VaultConfig;
+use crate::error::VaultError;
+use crate::state::{DepositRecord, VaultConfig};
 use anchor_lang::prelude::*;
 use anchor_lang::system_program;
 
@@ -12,9 +13,20 @@ pub struct DepositSol<'info> {
         mut,
         seeds = [VAULT_SEED, vault_config.creator.as_ref()],
         bump = vault_config.bump,
+        constraint = vault_config.version == VAULT_VERSION @ VaultError::AccountNotMigrated,
+        constraint = vault_config.deposit_policy.accepts_depositor(&depositor.key()) @ VaultError::DepositorNotAllowed,
     )]
     pub vault_config: Account<'info, VaultConfig>,
 
+    #[account(
+        init_if_needed,
+        payer = depositor,
+        space = 8 + DepositRecord::INIT_SPACE,
+        seeds = [DEPOSIT_SEED, vault_config.key().as_ref(), depositor.key().as_ref(), SOL_ASSET.as_ref()],
+        bump,
+    )]
+    pub deposit_record: Account<'info, DepositRecord>,
+
     pub system_program: Program<'info, System>,
 }
 
@@ -30,5 +42,13 @@ pub fn handler(ctx: Context<DepositSol>, amount: u64) -> Result<()> {
         amount,
     )?;
 
+    ctx.accounts.deposit_record.record(
+        ctx.accounts.vault_config.key(),
+        ctx.accounts.depositor.key(),
+        SOL_ASSET,
+        amount,
+        ctx.bumps.deposit_record,
+    )?;
+
     Ok(())
 }
@@ -1,5 +1,6 @@
 use crate::constants::*;
-use crate::state::VaultConfig;
+use crate::error::VaultError;
+use crate::state::{AssetRegistry, DepositRecord, VaultConfig};
 use anchor_lang::prelude::*;
 use anchor_spl::associated_token::AssociatedToken;
 use anchor_spl::token::{transfer, Mint, Token, TokenAccount, Transfer};
@@ -12,9 +13,14 @@ pub struct DepositToken<'info> {
     #[account(
         seeds = [VAULT_SEED, vault_config.creator.as_ref()],
         bump = vault_config.bump,
+        constraint = vault_config.version == VAULT_VERSION @ VaultError::AccountNotMigrated,
+        constraint = vault_config.deposit_policy.accepts_depositor(&depositor.key()) @ VaultError::DepositorNotAllowed,
     )]
     pub vault_config: Account<'info, VaultConfig>,
 
+    #[account(
+        constraint = vault_config.deposit_policy.accepts_mint(&mint.key()) @ VaultError::MintNotAllowed,
+    )]
     pub mint: Account<'info, Mint>,
 
     #[account(
@@ -32,12 +38,33 @@ pub struct DepositToken<'info> {
     )]
     pub vault_ata: Account<'info, TokenAccount>,
 
+    #[account(
+        init_if_needed,
+        payer = depositor,
+        space = 8 + DepositRecord::INIT_SPACE,
+        seeds = [DEPOSIT_SEED, vault_config.key().as_ref(), depositor.key().as_ref(), mint.key().as_ref()],
+        bump,
+    )]
+    pub deposit_record: Account<'info, DepositRecord>,
+
+    #[account(
+        init_if_needed,
+        payer = depositor,
+        space = 8 + AssetRegistry::INIT_SPACE,
+        seeds = [ASSET_REGISTRY_SEED, vault_config.key().as_ref()],
+        bump,
+    )]
+    pub asset_registry: Account<'info, AssetRegistry>,
+
     pub token_program: Program<'info, Token>,
     pub associated_token_program: Program<'info, AssociatedToken>,
     pub system_program: Program<'info, System>,
 }
 
 pub fn handler(ctx: Context<DepositToken>, amount: u64) -> Result<()> {
+    // A zero deposit would still register the mint in the asset registry.
+    require!(amount > 0, VaultError::InvalidAmount);
+
     transfer(
         CpiContext::new(
             ctx.accounts.token_program.to_account_info(),
@@ -50,5 +77,20 @@ pub fn handler(ctx: Context<DepositToken>, amount: u64) -> Result<()> {
         amount,
     )?;
 
+    ctx.accounts.deposit_record.record(
+        ctx.accounts.vault_config.key(),
+        ctx.accounts.depositor.key(),
+        ctx.accounts.mint.key(),
+        amount,
+        ctx.bumps.deposit_record,
+    )?;
+
+    ctx.accounts.asset_registry.track(
+        ctx.accounts.vault_config.key(),
+        ctx.accounts.mint.key(),
+        ctx.accounts.mint.decimals,
+        ctx.bumps.asset_registry,
+    )?;
+
     Ok(())
 }
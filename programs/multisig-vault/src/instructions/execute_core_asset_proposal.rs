@@ -0,0 +1,128 @@
+use crate::constants::*;
+use crate::error::VaultError;
+use crate::state::*;
+use anchor_lang::prelude::*;
+use mpl_core::instructions::TransferV1CpiBuilder;
+use pyth_solana_receiver_sdk::price_update::PriceUpdateV2;
+
+#[derive(Accounts)]
+pub struct ExecuteCoreAssetProposal<'info> {
+    #[account(mut)]
+    pub executor: Signer<'info>,
+
+    #[account(
+        seeds = [VAULT_SEED, vault_config.creator.as_ref()],
+        bump = vault_config.bump,
+        constraint = vault_config.version == VAULT_VERSION @ VaultError::AccountNotMigrated,
+        constraint = !vault_config.paused @ VaultError::VaultPaused,
+    )]
+    pub vault_config: Account<'info, VaultConfig>,
+
+    #[account(
+        mut,
+        seeds = [PROPOSAL_SEED, vault_config.key().as_ref(), proposal.proposal_id.to_le_bytes().as_ref()],
+        bump = proposal.bump,
+        constraint = proposal.version == PROPOSAL_VERSION @ VaultError::AccountNotMigrated,
+        constraint = proposal.vault == vault_config.key() @ VaultError::UnauthorizedSigner,
+    )]
+    pub proposal: Account<'info, Proposal>,
+
+    /// CHECK: validated against proposal.recipient in handler
+    pub recipient: UncheckedAccount<'info>,
+
+    /// CHECK: validated against the proposal in handler and by the core program
+    #[account(mut)]
+    pub asset: UncheckedAccount<'info>,
+
+    /// CHECK: asset collection, validated by the core program
+    pub collection: Option<UncheckedAccount<'info>>,
+
+    /// CHECK: Metaplex Core program
+    #[account(address = mpl_core::ID)]
+    pub mpl_core_program: UncheckedAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+
+    /// Optional Pyth price update account, validated in handler
+    pub price_update: Option<Account<'info, PriceUpdateV2>>,
+    /// CHECK: must match vault_config.policy_program, validated in handler
+    pub policy_program: Option<UncheckedAccount<'info>>,
+}
+
+pub fn handler(ctx: Context<ExecuteCoreAssetProposal>) -> Result<()> {
+    let proposal = &mut ctx.accounts.proposal;
+    let vault_config = &ctx.accounts.vault_config;
+
+    proposal.require_executable()?;
+    require!(
+        proposal.transfer_type
+            == TransferType::CoreAsset {
+                asset: ctx.accounts.asset.key()
+            },
+        VaultError::InvalidTransferType
+    );
+    require!(
+        vault_config.signers.contains(&ctx.accounts.executor.key()),
+        VaultError::UnauthorizedSigner
+    );
+    require!(
+        proposal.recipient == ctx.accounts.recipient.key(),
+        VaultError::UnauthorizedSigner
+    );
+
+    // Pyth price gate
+    if let Some(ref condition) = proposal.price_condition {
+        condition.verify(ctx.accounts.price_update.as_ref())?;
+    }
+
+    // PDA signer seeds for the vault
+    let creator = vault_config.creator;
+    let bump = [vault_config.bump];
+    let signer_seeds: &[&[&[u8]]] = &[&[VAULT_SEED, creator.as_ref(), &bump]];
+
+    let mpl_core_program = ctx.accounts.mpl_core_program.to_account_info();
+    let asset = ctx.accounts.asset.to_account_info();
+    let collection = ctx
+        .accounts
+        .collection
+        .as_ref()
+        .map(|a| a.to_account_info());
+    let executor = ctx.accounts.executor.to_account_info();
+    let vault_info = vault_config.to_account_info();
+    let recipient = ctx.accounts.recipient.to_account_info();
+    let system_program = ctx.accounts.system_program.to_account_info();
+
+    let balance = VaultConfig::available_lamports(&vault_info)?;
+    run_policy_check(
+        vault_config,
+        proposal,
+        &recipient,
+        ctx.accounts.policy_program.as_ref(),
+        PolicyStage::PreExecution,
+        1,
+        balance,
+    )?;
+
+    TransferV1CpiBuilder::new(&mpl_core_program)
+        .asset(&asset)
+        .collection(collection.as_ref())
+        .payer(&executor)
+        .authority(Some(&vault_info))
+        .new_owner(&recipient)
+        .system_program(Some(&system_program))
+        .invoke_signed(signer_seeds)?;
+
+    proposal.status = ProposalStatus::Executed;
+
+    run_policy_check(
+        vault_config,
+        proposal,
+        &recipient,
+        ctx.accounts.policy_program.as_ref(),
+        PolicyStage::PostExecution,
+        1,
+        VaultConfig::available_lamports(&vault_info)?,
+    )?;
+
+    Ok(())
+}
@@ -0,0 +1,299 @@
+use crate::constants::*;
+use crate::error::VaultError;
+use crate::instructions::migrate_vault::resize_account;
+use crate::state::*;
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+pub struct ExecuteGovernanceProposal<'info> {
+    /// Pays rent when `AddSigner` grows the vault account
+    #[account(mut)]
+    pub executor: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [VAULT_SEED, vault_config.creator.as_ref()],
+        bump = vault_config.bump,
+        constraint = vault_config.version == VAULT_VERSION @ VaultError::AccountNotMigrated,
+    )]
+    pub vault_config: Account<'info, VaultConfig>,
+
+    #[account(
+        mut,
+        seeds = [PROPOSAL_SEED, vault_config.key().as_ref(), proposal.proposal_id.to_le_bytes().as_ref()],
+        bump = proposal.bump,
+        constraint = proposal.version == PROPOSAL_VERSION @ VaultError::AccountNotMigrated,
+        constraint = proposal.vault == vault_config.key() @ VaultError::UnauthorizedSigner,
+    )]
+    pub proposal: Account<'info, Proposal>,
+
+    /// Only needed for `RemoveTrackedAsset` and `SetAssetPriceFeed`
+    #[account(
+        mut,
+        seeds = [ASSET_REGISTRY_SEED, vault_config.key().as_ref()],
+        bump = asset_registry.bump,
+    )]
+    pub asset_registry: Option<Account<'info, AssetRegistry>>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler(ctx: Context<ExecuteGovernanceProposal>) -> Result<()> {
+    let proposal = &mut ctx.accounts.proposal;
+    let vault = &mut ctx.accounts.vault_config;
+
+    proposal.require_executable()?;
+    require!(
+        vault.signers.contains(&ctx.accounts.executor.key()),
+        VaultError::UnauthorizedSigner
+    );
+
+    let TransferType::Governance { ref action } = proposal.transfer_type else {
+        return err!(VaultError::InvalidTransferType);
+    };
+
+    match action {
+        GovernanceAction::AddSigner { signer } => {
+            require!(!vault.signers.contains(signer), VaultError::DuplicateSigner);
+            require!(
+                vault.signers.len() < MAX_SIGNERS,
+                VaultError::TooManySigners
+            );
+            vault.signers.push(*signer);
+            vault.bump_config_epoch()?;
+            resize_account(
+                &vault.to_account_info(),
+                VaultConfig::space(vault.signers.len()),
+                &ctx.accounts.executor,
+                &ctx.accounts.system_program,
+            )?;
+        }
+        GovernanceAction::RemoveSigner { signer } => {
+            let index = vault
+                .signers
+                .iter()
+                .position(|s| s == signer)
+                .ok_or(error!(VaultError::SignerNotFound))?;
+            require!(
+                (vault.threshold as usize) < vault.signers.len(),
+                VaultError::InvalidThreshold
+            );
+            require!(
+                (vault.quorum as usize) < vault.signers.len(),
+                VaultError::InvalidQuorum
+            );
+            require!(
+                (vault.cancel_threshold as usize) < vault.signers.len(),
+                VaultError::InvalidThreshold
+            );
+            // Tiers and the elevated recipient threshold must stay reachable too
+            require!(
+                (vault.max_policy_threshold() as usize) < vault.signers.len(),
+                VaultError::InvalidThreshold
+            );
+            vault.signers.remove(index);
+            vault.bump_config_epoch()?;
+            resize_account(
+                &vault.to_account_info(),
+                VaultConfig::space(vault.signers.len()),
+                &ctx.accounts.executor,
+                &ctx.accounts.system_program,
+            )?;
+        }
+        GovernanceAction::AddRecipient { recipient } => {
+            require!(
+                !vault.address_book.contains(recipient),
+                VaultError::RecipientAlreadyListed
+            );
+            require!(
+                vault.address_book.len() < MAX_ADDRESS_BOOK,
+                VaultError::AddressBookFull
+            );
+            vault.address_book.push(*recipient);
+        }
+        GovernanceAction::RemoveRecipient { recipient } => {
+            let index = vault
+                .address_book
+                .iter()
+                .position(|r| r == recipient)
+                .ok_or(error!(VaultError::RecipientNotListed))?;
+            vault.address_book.remove(index);
+        }
+        GovernanceAction::SetRecipientPolicy { policy } => {
+            if let RecipientPolicy::ElevatedThreshold { threshold } = policy {
+                require!(
+                    *threshold >= 1 && *threshold as usize <= vault.signers.len(),
+                    VaultError::InvalidThreshold
+                );
+            }
+            vault.recipient_policy = *policy;
+        }
+        GovernanceAction::AddThresholdTier { tier } => {
+            require!(
+                tier.threshold >= 1 && tier.threshold as usize <= vault.signers.len(),
+                VaultError::InvalidThreshold
+            );
+            vault
+                .threshold_tiers
+                .retain(|t| !(t.mint == tier.mint && t.min_amount == tier.min_amount));
+            require!(
+                vault.threshold_tiers.len() < MAX_THRESHOLD_TIERS,
+                VaultError::TooManyThresholdTiers
+            );
+            vault.threshold_tiers.push(*tier);
+        }
+        GovernanceAction::RemoveThresholdTier { mint, min_amount } => {
+            let index = vault
+                .threshold_tiers
+                .iter()
+                .position(|t| t.mint == *mint && t.min_amount == *min_amount)
+                .ok_or(error!(VaultError::ThresholdTierNotFound))?;
+            vault.threshold_tiers.remove(index);
+        }
+        GovernanceAction::SetGuardian { guardian } => {
+            vault.guardian = *guardian;
+        }
+        GovernanceAction::Unpause => {
+            vault.paused = false;
+        }
+        GovernanceAction::AddVetoMember { member } => {
+            require!(
+                !vault.veto_council.contains(member),
+                VaultError::VetoMemberAlreadyListed
+            );
+            require!(
+                vault.veto_council.len() < MAX_VETO_COUNCIL,
+                VaultError::VetoCouncilFull
+            );
+            vault.veto_council.push(*member);
+        }
+        GovernanceAction::RemoveVetoMember { member } => {
+            let index = vault
+                .veto_council
+                .iter()
+                .position(|m| m == member)
+                .ok_or(error!(VaultError::VetoMemberNotListed))?;
+            vault.veto_council.remove(index);
+        }
+        GovernanceAction::SetQuorum { quorum } => {
+            require!(
+                *quorum as usize <= vault.signers.len(),
+                VaultError::InvalidQuorum
+            );
+            // Without a quorum a lone proposer could pass by majority
+            require!(
+                *quorum > 0 || vault.deadline_rule != DeadlineRule::SimpleMajority,
+                VaultError::InvalidQuorum
+            );
+            vault.quorum = *quorum;
+        }
+        GovernanceAction::SetVotingPeriod {
+            voting_period_secs,
+            deadline_rule,
+        } => {
+            require!(
+                (0..=MAX_VOTING_PERIOD_SECS).contains(voting_period_secs),
+                VaultError::InvalidVotingPeriod
+            );
+            require!(
+                vault.quorum > 0 || *deadline_rule != DeadlineRule::SimpleMajority,
+                VaultError::InvalidQuorum
+            );
+            vault.voting_period_secs = *voting_period_secs;
+            vault.deadline_rule = *deadline_rule;
+        }
+        GovernanceAction::SetCancelThreshold { threshold } => {
+            require!(
+                *threshold >= 1 && *threshold as usize <= vault.signers.len(),
+                VaultError::InvalidThreshold
+            );
+            vault.cancel_threshold = *threshold;
+        }
+        GovernanceAction::SetTimelock { timelock_secs } => {
+            require!(
+                (0..=MAX_TIMELOCK_SECS).contains(timelock_secs),
+                VaultError::InvalidTimelock
+            );
+            vault.timelock_secs = *timelock_secs;
+        }
+        GovernanceAction::SetRecovery { recovery } => {
+            if let Some(recovery) = recovery {
+                recovery.validate()?;
+            }
+            vault.recovery = recovery.clone();
+        }
+        GovernanceAction::SetShareAutoApproval { max_shares } => {
+            let pool = vault
+                .share_pool
+                .as_mut()
+                .ok_or(error!(VaultError::SharePoolNotInitialized))?;
+            pool.auto_approve_max_shares = *max_shares;
+        }
+        GovernanceAction::AddAllowedMint { mint } => {
+            add_to_allowlist(
+                &mut vault.deposit_policy.allowed_mints,
+                *mint,
+                MAX_ALLOWED_MINTS,
+            )?;
+        }
+        GovernanceAction::RemoveAllowedMint { mint } => {
+            remove_from_allowlist(&mut vault.deposit_policy.allowed_mints, mint)?;
+        }
+        GovernanceAction::AddAllowedDepositor { depositor } => {
+            add_to_allowlist(
+                &mut vault.deposit_policy.allowed_depositors,
+                *depositor,
+                MAX_ALLOWED_DEPOSITORS,
+            )?;
+        }
+        GovernanceAction::RemoveAllowedDepositor { depositor } => {
+            remove_from_allowlist(&mut vault.deposit_policy.allowed_depositors, depositor)?;
+        }
+        GovernanceAction::RemoveTrackedAsset { mint } => {
+            let registry = ctx
+                .accounts
+                .asset_registry
+                .as_mut()
+                .ok_or(error!(VaultError::AssetRegistryRequired))?;
+            registry.untrack(mint)?;
+        }
+        GovernanceAction::SetAssetPriceFeed { mint, feed_id } => {
+            let registry = ctx
+                .accounts
+                .asset_registry
+                .as_mut()
+                .ok_or(error!(VaultError::AssetRegistryRequired))?;
+            registry.set_price_feed(mint, *feed_id)?;
+        }
+        GovernanceAction::SetDepositRestrictions {
+            restrict_mints,
+            restrict_depositors,
+        } => {
+            vault.deposit_policy.restrict_mints = *restrict_mints;
+            vault.deposit_policy.restrict_depositors = *restrict_depositors;
+        }
+        GovernanceAction::SetPolicyProgram { program } => {
+            vault.policy_program = *program;
+        }
+    }
+
+    proposal.status = ProposalStatus::Executed;
+
+    Ok(())
+}
+
+fn add_to_allowlist(list: &mut Vec<Pubkey>, key: Pubkey, max_len: usize) -> Result<()> {
+    require!(!list.contains(&key), VaultError::AlreadyOnDepositAllowlist);
+    require!(list.len() < max_len, VaultError::DepositAllowlistFull);
+    list.push(key);
+    Ok(())
+}
+
+fn remove_from_allowlist(list: &mut Vec<Pubkey>, key: &Pubkey) -> Result<()> {
+    let index = list
+        .iter()
+        .position(|k| k == key)
+        .ok_or(error!(VaultError::NotOnDepositAllowlist))?;
+    list.remove(index);
+    Ok(())
+}
@@ -0,0 +1,196 @@
+use crate::constants::*;
+use crate::error::VaultError;
+use crate::state::*;
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::sysvar;
+use anchor_spl::associated_token::AssociatedToken;
+use anchor_spl::metadata::mpl_token_metadata::instructions::TransferV1CpiBuilder;
+use anchor_spl::metadata::Metadata;
+use anchor_spl::token::{Mint, Token, TokenAccount};
+use pyth_solana_receiver_sdk::price_update::PriceUpdateV2;
+
+#[derive(Accounts)]
+pub struct ExecuteNftProposal<'info> {
+    #[account(mut)]
+    pub executor: Signer<'info>,
+
+    #[account(
+        seeds = [VAULT_SEED, vault_config.creator.as_ref()],
+        bump = vault_config.bump,
+        constraint = vault_config.version == VAULT_VERSION @ VaultError::AccountNotMigrated,
+        constraint = !vault_config.paused @ VaultError::VaultPaused,
+    )]
+    pub vault_config: Account<'info, VaultConfig>,
+
+    #[account(
+        mut,
+        seeds = [PROPOSAL_SEED, vault_config.key().as_ref(), proposal.proposal_id.to_le_bytes().as_ref()],
+        bump = proposal.bump,
+        constraint = proposal.version == PROPOSAL_VERSION @ VaultError::AccountNotMigrated,
+        constraint = proposal.vault == vault_config.key() @ VaultError::UnauthorizedSigner,
+    )]
+    pub proposal: Account<'info, Proposal>,
+
+    /// CHECK: validated against proposal.recipient in handler
+    pub recipient: UncheckedAccount<'info>,
+
+    pub mint: Account<'info, Mint>,
+
+    #[account(
+        mut,
+        token::mint = mint,
+        token::authority = vault_config,
+    )]
+    pub vault_token_account: Account<'info, TokenAccount>,
+
+    /// CHECK: recipient ATA, derived and created by the token metadata program
+    #[account(mut)]
+    pub recipient_ata: UncheckedAccount<'info>,
+
+    /// CHECK: validated by the token metadata program
+    #[account(mut)]
+    pub metadata: UncheckedAccount<'info>,
+
+    /// CHECK: master edition, validated by the token metadata program
+    pub edition: Option<UncheckedAccount<'info>>,
+
+    /// CHECK: pNFT token record of the vault token account
+    #[account(mut)]
+    pub token_record: Option<UncheckedAccount<'info>>,
+
+    /// CHECK: pNFT token record of the recipient ATA
+    #[account(mut)]
+    pub destination_token_record: Option<UncheckedAccount<'info>>,
+
+    /// CHECK: pNFT rule set, validated by the token metadata program
+    pub authorization_rules: Option<UncheckedAccount<'info>>,
+
+    /// CHECK: token auth rules program, validated by the token metadata program
+    pub authorization_rules_program: Option<UncheckedAccount<'info>>,
+
+    /// CHECK: instructions sysvar
+    #[account(address = sysvar::instructions::ID)]
+    pub sysvar_instructions: UncheckedAccount<'info>,
+
+    pub token_metadata_program: Program<'info, Metadata>,
+    pub token_program: Program<'info, Token>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+
+    /// Optional Pyth price update account, validated in handler
+    pub price_update: Option<Account<'info, PriceUpdateV2>>,
+    /// CHECK: must match vault_config.policy_program, validated in handler
+    pub policy_program: Option<UncheckedAccount<'info>>,
+}
+
+pub fn handler(ctx: Context<ExecuteNftProposal>) -> Result<()> {
+    let proposal = &mut ctx.accounts.proposal;
+    let vault_config = &ctx.accounts.vault_config;
+
+    proposal.require_executable()?;
+    require!(
+        proposal.transfer_type
+            == TransferType::Nft {
+                mint: ctx.accounts.mint.key()
+            },
+        VaultError::InvalidTransferType
+    );
+    require!(
+        vault_config.signers.contains(&ctx.accounts.executor.key()),
+        VaultError::UnauthorizedSigner
+    );
+    require!(
+        proposal.recipient == ctx.accounts.recipient.key(),
+        VaultError::UnauthorizedSigner
+    );
+
+    // Pyth price gate
+    if let Some(ref condition) = proposal.price_condition {
+        condition.verify(ctx.accounts.price_update.as_ref())?;
+    }
+
+    // PDA signer seeds for the vault
+    let creator = vault_config.creator;
+    let bump = [vault_config.bump];
+    let signer_seeds: &[&[&[u8]]] = &[&[VAULT_SEED, creator.as_ref(), &bump]];
+
+    let token_metadata_program = ctx.accounts.token_metadata_program.to_account_info();
+    let vault_token_account = ctx.accounts.vault_token_account.to_account_info();
+    let vault_info = vault_config.to_account_info();
+    let recipient_ata = ctx.accounts.recipient_ata.to_account_info();
+    let recipient = ctx.accounts.recipient.to_account_info();
+    let mint = ctx.accounts.mint.to_account_info();
+    let metadata = ctx.accounts.metadata.to_account_info();
+    let executor = ctx.accounts.executor.to_account_info();
+    let system_program = ctx.accounts.system_program.to_account_info();
+    let sysvar_instructions = ctx.accounts.sysvar_instructions.to_account_info();
+    let token_program = ctx.accounts.token_program.to_account_info();
+    let associated_token_program = ctx.accounts.associated_token_program.to_account_info();
+    let edition = ctx.accounts.edition.as_ref().map(|a| a.to_account_info());
+    let token_record = ctx
+        .accounts
+        .token_record
+        .as_ref()
+        .map(|a| a.to_account_info());
+    let destination_token_record = ctx
+        .accounts
+        .destination_token_record
+        .as_ref()
+        .map(|a| a.to_account_info());
+    let authorization_rules = ctx
+        .accounts
+        .authorization_rules
+        .as_ref()
+        .map(|a| a.to_account_info());
+    let authorization_rules_program = ctx
+        .accounts
+        .authorization_rules_program
+        .as_ref()
+        .map(|a| a.to_account_info());
+
+    let balance = ctx.accounts.vault_token_account.amount;
+    run_policy_check(
+        vault_config,
+        proposal,
+        &recipient,
+        ctx.accounts.policy_program.as_ref(),
+        PolicyStage::PreExecution,
+        1,
+        balance,
+    )?;
+
+    TransferV1CpiBuilder::new(&token_metadata_program)
+        .token(&vault_token_account)
+        .token_owner(&vault_info)
+        .destination_token(&recipient_ata)
+        .destination_owner(&recipient)
+        .mint(&mint)
+        .metadata(&metadata)
+        .edition(edition.as_ref())
+        .token_record(token_record.as_ref())
+        .destination_token_record(destination_token_record.as_ref())
+        .authority(&vault_info)
+        .payer(&executor)
+        .system_program(&system_program)
+        .sysvar_instructions(&sysvar_instructions)
+        .spl_token_program(&token_program)
+        .spl_ata_program(&associated_token_program)
+        .authorization_rules(authorization_rules.as_ref())
+        .authorization_rules_program(authorization_rules_program.as_ref())
+        .amount(1)
+        .invoke_signed(signer_seeds)?;
+
+    proposal.status = ProposalStatus::Executed;
+
+    run_policy_check(
+        vault_config,
+        proposal,
+        &recipient,
+        ctx.accounts.policy_program.as_ref(),
+        PolicyStage::PostExecution,
+        1,
+        balance - 1,
+    )?;
+
+    Ok(())
+}
@@ -0,0 +1,73 @@
+use crate::constants::*;
+use crate::error::VaultError;
+use crate::instructions::migrate_vault::resize_account;
+use crate::state::*;
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+pub struct ExecuteRecovery<'info> {
+    /// Pays rent if the new signer set needs a larger vault account
+    #[account(mut)]
+    pub executor: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [VAULT_SEED, vault_config.creator.as_ref()],
+        bump = vault_config.bump,
+        constraint = vault_config.version == VAULT_VERSION @ VaultError::AccountNotMigrated,
+    )]
+    pub vault_config: Account<'info, VaultConfig>,
+
+    #[account(
+        mut,
+        close = initiator,
+        seeds = [RECOVERY_SEED, vault_config.key().as_ref()],
+        bump = recovery_request.bump,
+        has_one = initiator,
+    )]
+    pub recovery_request: Account<'info, RecoveryRequest>,
+
+    /// CHECK: rent refund destination, validated by has_one
+    #[account(mut)]
+    pub initiator: UncheckedAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler(ctx: Context<ExecuteRecovery>) -> Result<()> {
+    let request = &ctx.accounts.recovery_request;
+    let vault = &mut ctx.accounts.vault_config;
+
+    // The recovery config may have changed since the request was approved.
+    let recovery = vault
+        .recovery
+        .as_ref()
+        .ok_or(error!(VaultError::RecoveryNotConfigured))?;
+    require!(
+        RecoveryRequest::count_current(&request.approvals, recovery) >= recovery.threshold as usize,
+        VaultError::RecoveryNotApproved
+    );
+
+    // A delay raised after approval applies to this request too.
+    let executable_at = request
+        .ready_at(recovery.delay_secs)?
+        .ok_or(error!(VaultError::RecoveryNotApproved))?;
+    require!(
+        Clock::get()?.unix_timestamp >= executable_at,
+        VaultError::RecoveryDelayActive
+    );
+
+    vault.signers = request.new_signers.clone();
+    vault.threshold = request.new_threshold;
+    vault.cancel_threshold = request.new_threshold;
+    vault.clamp_to_signers();
+    vault.bump_config_epoch()?;
+    resize_account(
+        &vault.to_account_info(),
+        VaultConfig::space(vault.signers.len()),
+        &ctx.accounts.executor,
+        &ctx.accounts.system_program,
+    )?;
+
+    Ok(())
+}
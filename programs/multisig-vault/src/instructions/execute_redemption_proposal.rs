@@ -0,0 +1,95 @@
+use crate::constants::*;
+use crate::error::VaultError;
+use crate::instructions::redeem_shares::burn_and_pay;
+use crate::state::*;
+use anchor_lang::prelude::*;
+use anchor_spl::token::{Mint, Token, TokenAccount};
+
+#[derive(Accounts)]
+pub struct ExecuteRedemptionProposal<'info> {
+    /// Share holder; signs the burn of their shares
+    #[account(mut)]
+    pub holder: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [VAULT_SEED, vault_config.creator.as_ref()],
+        bump = vault_config.bump,
+        constraint = vault_config.version == VAULT_VERSION @ VaultError::AccountNotMigrated,
+        constraint = !vault_config.paused @ VaultError::VaultPaused,
+    )]
+    pub vault_config: Account<'info, VaultConfig>,
+
+    #[account(
+        mut,
+        seeds = [PROPOSAL_SEED, vault_config.key().as_ref(), proposal.proposal_id.to_le_bytes().as_ref()],
+        bump = proposal.bump,
+        constraint = proposal.version == PROPOSAL_VERSION @ VaultError::AccountNotMigrated,
+        constraint = proposal.vault == vault_config.key() @ VaultError::UnauthorizedSigner,
+        constraint = proposal.recipient == holder.key() @ VaultError::UnauthorizedSigner,
+    )]
+    pub proposal: Account<'info, Proposal>,
+
+    #[account(
+        mut,
+        seeds = [SHARE_MINT_SEED, vault_config.key().as_ref()],
+        bump,
+    )]
+    pub share_mint: Account<'info, Mint>,
+
+    #[account(
+        mut,
+        token::mint = share_mint,
+        token::authority = holder,
+    )]
+    pub holder_share_account: Account<'info, TokenAccount>,
+
+    pub token_program: Program<'info, Token>,
+    /// CHECK: must match vault_config.policy_program, validated in handler
+    pub policy_program: Option<UncheckedAccount<'info>>,
+}
+
+pub fn handler(ctx: Context<ExecuteRedemptionProposal>) -> Result<()> {
+    let proposal = &ctx.accounts.proposal;
+
+    proposal.require_executable()?;
+    require!(
+        proposal.transfer_type == TransferType::RedeemShares,
+        VaultError::InvalidTransferType
+    );
+
+    let vault_info = ctx.accounts.vault_config.to_account_info();
+    let holder_info = ctx.accounts.holder.to_account_info();
+    run_policy_check(
+        &ctx.accounts.vault_config,
+        proposal,
+        &holder_info,
+        ctx.accounts.policy_program.as_ref(),
+        PolicyStage::PreExecution,
+        proposal.amount,
+        VaultConfig::available_lamports(&vault_info)?,
+    )?;
+
+    burn_and_pay(
+        &ctx.accounts.token_program,
+        &ctx.accounts.share_mint,
+        &ctx.accounts.holder_share_account,
+        &ctx.accounts.holder,
+        &mut ctx.accounts.vault_config,
+        proposal.amount,
+    )?;
+
+    ctx.accounts.proposal.status = ProposalStatus::Executed;
+
+    run_policy_check(
+        &ctx.accounts.vault_config,
+        &ctx.accounts.proposal,
+        &holder_info,
+        ctx.accounts.policy_program.as_ref(),
+        PolicyStage::PostExecution,
+        ctx.accounts.proposal.amount,
+        VaultConfig::available_lamports(&vault_info)?,
+    )?;
+
+    Ok(())
+}
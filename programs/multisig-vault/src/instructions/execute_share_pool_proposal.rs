@@ -0,0 +1,73 @@
+use crate::constants::*;
+use crate::error::VaultError;
+use crate::state::*;
+use anchor_lang::prelude::*;
+use anchor_spl::token::{Mint, Token};
+
+#[derive(Accounts)]
+pub struct ExecuteSharePoolProposal<'info> {
+    #[account(mut)]
+    pub executor: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [VAULT_SEED, vault_config.creator.as_ref()],
+        bump = vault_config.bump,
+        constraint = vault_config.version == VAULT_VERSION @ VaultError::AccountNotMigrated,
+    )]
+    pub vault_config: Account<'info, VaultConfig>,
+
+    #[account(
+        mut,
+        seeds = [PROPOSAL_SEED, vault_config.key().as_ref(), proposal.proposal_id.to_le_bytes().as_ref()],
+        bump = proposal.bump,
+        constraint = proposal.version == PROPOSAL_VERSION @ VaultError::AccountNotMigrated,
+        constraint = proposal.vault == vault_config.key() @ VaultError::UnauthorizedSigner,
+    )]
+    pub proposal: Account<'info, Proposal>,
+
+    #[account(
+        init,
+        payer = executor,
+        seeds = [SHARE_MINT_SEED, vault_config.key().as_ref()],
+        bump,
+        mint::decimals = SHARE_DECIMALS,
+        mint::authority = vault_config,
+    )]
+    pub share_mint: Account<'info, Mint>,
+
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler(ctx: Context<ExecuteSharePoolProposal>) -> Result<()> {
+    let proposal = &mut ctx.accounts.proposal;
+    let vault_config = &ctx.accounts.vault_config;
+
+    proposal.require_executable()?;
+    require!(
+        vault_config.signers.contains(&ctx.accounts.executor.key()),
+        VaultError::UnauthorizedSigner
+    );
+
+    let TransferType::CreateSharePool {
+        auto_approve_max_shares,
+    } = proposal.transfer_type
+    else {
+        return err!(VaultError::InvalidTransferType);
+    };
+
+    // The pool starts empty. SOL the vault already holds stays outside it, so
+    // depositors can't claim it and proposals can still spend it.
+    ctx.accounts.vault_config.share_pool = Some(SharePool {
+        mint: ctx.accounts.share_mint.key(),
+        auto_approve_max_shares,
+        mint_bump: ctx.bumps.share_mint,
+        pool_lamports: 0,
+        window_start: 0,
+        redeemed_in_window: 0,
+    });
+    proposal.status = ProposalStatus::Executed;
+
+    Ok(())
+}
@@ -12,6 +12,8 @@ pub struct ExecuteSolProposal<'info> {
         mut,
         seeds = [VAULT_SEED, vault_config.creator.as_ref()],
         bump = vault_config.bump,
+        constraint = vault_config.version == VAULT_VERSION @ VaultError::AccountNotMigrated,
+        constraint = !vault_config.paused @ VaultError::VaultPaused,
     )]
     pub vault_config: Account<'info, VaultConfig>,
 
@@ -19,6 +21,7 @@ pub struct ExecuteSolProposal<'info> {
         mut,
         seeds = [PROPOSAL_SEED, vault_config.key().as_ref(), proposal.proposal_id.to_le_bytes().as_ref()],
         bump = proposal.bump,
+        constraint = proposal.version == PROPOSAL_VERSION @ VaultError::AccountNotMigrated,
         constraint = proposal.vault == vault_config.key() @ VaultError::UnauthorizedSigner,
     )]
     pub proposal: Account<'info, Proposal>,
@@ -31,15 +34,18 @@ pub struct ExecuteSolProposal<'info> {
     pub price_update: Option<Account<'info, PriceUpdateV2>>,
 
     pub system_program: Program<'info, System>,
+    /// CHECK: must match vault_config.policy_program, validated in handler
+    pub policy_program: Option<UncheckedAccount<'info>>,
 }
 
 pub fn handler(ctx: Context<ExecuteSolProposal>) -> Result<()> {
     let proposal = &mut ctx.accounts.proposal;
     let vault_config = &ctx.accounts.vault_config;
 
+    proposal.require_executable()?;
     require!(
-        proposal.status == ProposalStatus::Approved,
-        VaultError::ProposalNotActive
+        proposal.transfer_type == TransferType::Sol,
+        VaultError::InvalidTransferType
     );
     require!(
         vault_config.signers.contains(&ctx.accounts.executor.key()),
@@ -52,38 +58,40 @@ pub fn handler(ctx: Context<ExecuteSolProposal>) -> Result<()> {
 
     // Pyth price gate
     if let Some(ref condition) = proposal.price_condition {
-        let price_update = ctx
-            .accounts
-            .price_update
-            .as_ref()
-            .ok_or(error!(VaultError::PriceConditionNotMet))?;
-
-        let price = price_update
-            .get_price_no_older_than(&Clock::get()?, condition.max_age_secs, &condition.feed_id)
-            .map_err(|_| error!(VaultError::StalePriceFeed))?;
-
-        if let Some(min_price) = condition.min_price {
-            require!(price.price >= min_price, VaultError::PriceConditionNotMet);
-        }
-        if let Some(max_price) = condition.max_price {
-            require!(price.price <= max_price, VaultError::PriceConditionNotMet);
-        }
+        condition.verify(ctx.accounts.price_update.as_ref())?;
     }
 
-    let vault_balance = vault_config.to_account_info().lamports();
-    let rent_exempt_min = Rent::get()?.minimum_balance(8 + VaultConfig::INIT_SPACE);
-    let available = vault_balance.saturating_sub(rent_exempt_min);
-
-    require!(available >= proposal.amount, VaultError::InsufficientBalance);
+    let available = vault_config.spendable_lamports(&vault_config.to_account_info())?;
+    let payment = proposal.next_payment(available)?;
 
     // Transfer SOL from vault PDA to recipient
     let vault_info = vault_config.to_account_info();
     let recipient_info = ctx.accounts.recipient.to_account_info();
 
-    **vault_info.try_borrow_mut_lamports()? -= proposal.amount;
-    **recipient_info.try_borrow_mut_lamports()? += proposal.amount;
-
-    proposal.status = ProposalStatus::Executed;
+    run_policy_check(
+        vault_config,
+        proposal,
+        &recipient_info,
+        ctx.accounts.policy_program.as_ref(),
+        PolicyStage::PreExecution,
+        payment,
+        available,
+    )?;
+
+    **vault_info.try_borrow_mut_lamports()? -= payment;
+    **recipient_info.try_borrow_mut_lamports()? += payment;
+
+    proposal.record_payment(payment);
+
+    run_policy_check(
+        vault_config,
+        proposal,
+        &recipient_info,
+        ctx.accounts.policy_program.as_ref(),
+        PolicyStage::PostExecution,
+        payment,
+        available - payment,
+    )?;
 
     Ok(())
 }
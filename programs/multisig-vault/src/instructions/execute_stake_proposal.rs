@@ -0,0 +1,262 @@
+use crate::constants::*;
+use crate::error::VaultError;
+use crate::state::*;
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::program::{invoke, invoke_signed};
+use anchor_lang::solana_program::sysvar;
+use anchor_lang::system_program::{allocate, assign, Allocate, Assign};
+use solana_stake_interface::instruction as stake_instruction;
+use solana_stake_interface::state::{Authorized, Lockup, StakeStateV2};
+
+#[derive(Accounts)]
+pub struct ExecuteStakeProposal<'info> {
+    pub executor: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [VAULT_SEED, vault_config.creator.as_ref()],
+        bump = vault_config.bump,
+        constraint = vault_config.version == VAULT_VERSION @ VaultError::AccountNotMigrated,
+        constraint = !vault_config.paused @ VaultError::VaultPaused,
+    )]
+    pub vault_config: Account<'info, VaultConfig>,
+
+    #[account(
+        mut,
+        seeds = [PROPOSAL_SEED, vault_config.key().as_ref(), proposal.proposal_id.to_le_bytes().as_ref()],
+        bump = proposal.bump,
+        constraint = proposal.version == PROPOSAL_VERSION @ VaultError::AccountNotMigrated,
+        constraint = proposal.vault == vault_config.key() @ VaultError::UnauthorizedSigner,
+    )]
+    pub proposal: Account<'info, Proposal>,
+
+    /// CHECK: derived from the proposal for CreateStake, matched against it otherwise
+    #[account(mut)]
+    pub stake_account: UncheckedAccount<'info>,
+
+    /// CHECK: matched against the proposal, required for CreateStake and DelegateStake
+    pub vote_account: Option<UncheckedAccount<'info>>,
+
+    /// CHECK: stake history sysvar
+    #[account(address = sysvar::stake_history::ID)]
+    pub stake_history: UncheckedAccount<'info>,
+
+    /// CHECK: stake config account, still required by DelegateStake
+    #[account(address = solana_stake_interface::config::ID)]
+    pub stake_config: UncheckedAccount<'info>,
+
+    /// CHECK: stake program
+    #[account(address = solana_stake_interface::program::ID)]
+    pub stake_program: UncheckedAccount<'info>,
+
+    pub clock: Sysvar<'info, Clock>,
+    pub rent: Sysvar<'info, Rent>,
+    pub system_program: Program<'info, System>,
+    /// CHECK: must match vault_config.policy_program, validated in handler
+    pub policy_program: Option<UncheckedAccount<'info>>,
+}
+
+pub fn handler(ctx: Context<ExecuteStakeProposal>) -> Result<()> {
+    let proposal = &ctx.accounts.proposal;
+    let vault_config = &ctx.accounts.vault_config;
+
+    proposal.require_executable()?;
+    require!(
+        vault_config.signers.contains(&ctx.accounts.executor.key()),
+        VaultError::UnauthorizedSigner
+    );
+
+    let vault_info = vault_config.to_account_info();
+    let stake_info = ctx.accounts.stake_account.to_account_info();
+    run_policy_check(
+        vault_config,
+        proposal,
+        &stake_info,
+        ctx.accounts.policy_program.as_ref(),
+        PolicyStage::PreExecution,
+        proposal.amount,
+        vault_config.spendable_lamports(&vault_info)?,
+    )?;
+
+    match proposal.transfer_type {
+        TransferType::CreateStake { vote_account } => {
+            create_stake(&ctx)?;
+            delegate(&ctx, vote_account)?;
+        }
+        TransferType::DelegateStake {
+            stake_account,
+            vote_account,
+        } => {
+            require_stake_account(&ctx, stake_account)?;
+            delegate(&ctx, vote_account)?;
+        }
+        TransferType::DeactivateStake { stake_account } => {
+            require_stake_account(&ctx, stake_account)?;
+            invoke_signed(
+                &stake_instruction::deactivate_stake(&stake_account, &vault_config.key()),
+                &[
+                    ctx.accounts.stake_account.to_account_info(),
+                    ctx.accounts.clock.to_account_info(),
+                    vault_config.to_account_info(),
+                ],
+                &[&vault_seeds(vault_config)],
+            )?;
+        }
+        TransferType::WithdrawStake { stake_account } => {
+            require_stake_account(&ctx, stake_account)?;
+            invoke_signed(
+                &stake_instruction::withdraw(
+                    &stake_account,
+                    &vault_config.key(),
+                    &vault_config.key(),
+                    proposal.amount,
+                    None,
+                ),
+                &[
+                    ctx.accounts.stake_account.to_account_info(),
+                    vault_config.to_account_info(),
+                    ctx.accounts.clock.to_account_info(),
+                    ctx.accounts.stake_history.to_account_info(),
+                    vault_config.to_account_info(),
+                ],
+                &[&vault_seeds(vault_config)],
+            )?;
+        }
+        _ => return err!(VaultError::InvalidTransferType),
+    }
+
+    ctx.accounts.proposal.status = ProposalStatus::Executed;
+
+    run_policy_check(
+        &ctx.accounts.vault_config,
+        &ctx.accounts.proposal,
+        &stake_info,
+        ctx.accounts.policy_program.as_ref(),
+        PolicyStage::PostExecution,
+        ctx.accounts.proposal.amount,
+        ctx.accounts.vault_config.spendable_lamports(&vault_info)?,
+    )?;
+
+    Ok(())
+}
+
+fn vault_seeds(vault_config: &VaultConfig) -> [&[u8]; 3] {
+    [
+        VAULT_SEED,
+        vault_config.creator.as_ref(),
+        std::slice::from_ref(&vault_config.bump),
+    ]
+}
+
+fn require_stake_account(ctx: &Context<ExecuteStakeProposal>, stake_account: Pubkey) -> Result<()> {
+    require_keys_eq!(
+        ctx.accounts.stake_account.key(),
+        stake_account,
+        VaultError::InvalidStakeAccount
+    );
+    Ok(())
+}
+
+/// Moves `amount` plus rent from the vault into the proposal's stake PDA and
+/// initializes it with the vault as staker and withdrawer.
+fn create_stake(ctx: &Context<ExecuteStakeProposal>) -> Result<()> {
+    let vault_config = &ctx.accounts.vault_config;
+    let proposal_key = ctx.accounts.proposal.key();
+    let stake_info = ctx.accounts.stake_account.to_account_info();
+
+    let (expected, stake_bump) =
+        Pubkey::find_program_address(&[STAKE_SEED, proposal_key.as_ref()], ctx.program_id);
+    require_keys_eq!(stake_info.key(), expected, VaultError::InvalidStakeAccount);
+
+    let space = StakeStateV2::size_of();
+    let total = ctx
+        .accounts
+        .proposal
+        .amount
+        .checked_add(ctx.accounts.rent.minimum_balance(space))
+        .ok_or(error!(VaultError::ArithmeticOverflow))?;
+    let vault_info = vault_config.to_account_info();
+    require!(
+        vault_config.spendable_lamports(&vault_info)? >= total,
+        VaultError::InsufficientBalance
+    );
+
+    let stake_seeds: &[&[&[u8]]] = &[&[STAKE_SEED, proposal_key.as_ref(), &[stake_bump]]];
+    allocate(
+        CpiContext::new_with_signer(
+            ctx.accounts.system_program.to_account_info(),
+            Allocate {
+                account_to_allocate: stake_info.clone(),
+            },
+            stake_seeds,
+        ),
+        space as u64,
+    )?;
+    assign(
+        CpiContext::new_with_signer(
+            ctx.accounts.system_program.to_account_info(),
+            Assign {
+                account_to_assign: stake_info.clone(),
+            },
+            stake_seeds,
+        ),
+        &solana_stake_interface::program::ID,
+    )?;
+
+    **vault_info.try_borrow_mut_lamports()? -= total;
+    **stake_info.try_borrow_mut_lamports()? += total;
+
+    // The runtime checks lamport balances across a CPI using only the
+    // accounts passed to it, so the debited vault rides along as an extra
+    // account that initialize ignores.
+    let mut initialize_ix = stake_instruction::initialize(
+        &stake_info.key(),
+        &Authorized {
+            staker: vault_config.key(),
+            withdrawer: vault_config.key(),
+        },
+        &Lockup::default(),
+    );
+    initialize_ix
+        .accounts
+        .push(AccountMeta::new(vault_info.key(), false));
+    invoke(
+        &initialize_ix,
+        &[stake_info, ctx.accounts.rent.to_account_info(), vault_info],
+    )?;
+
+    Ok(())
+}
+
+fn delegate(ctx: &Context<ExecuteStakeProposal>, vote_account: Pubkey) -> Result<()> {
+    let vault_config = &ctx.accounts.vault_config;
+    let vote_info = ctx
+        .accounts
+        .vote_account
+        .as_ref()
+        .ok_or(error!(VaultError::InvalidVoteAccount))?;
+    require_keys_eq!(
+        vote_info.key(),
+        vote_account,
+        VaultError::InvalidVoteAccount
+    );
+
+    invoke_signed(
+        &stake_instruction::delegate_stake(
+            &ctx.accounts.stake_account.key(),
+            &vault_config.key(),
+            &vote_account,
+        ),
+        &[
+            ctx.accounts.stake_account.to_account_info(),
+            vote_info.to_account_info(),
+            ctx.accounts.clock.to_account_info(),
+            ctx.accounts.stake_history.to_account_info(),
+            ctx.accounts.stake_config.to_account_info(),
+            vault_config.to_account_info(),
+        ],
+        &[&vault_seeds(vault_config)],
+    )?;
+
+    Ok(())
+}
@@ -0,0 +1,148 @@
+use crate::constants::*;
+use crate::error::VaultError;
+use crate::state::*;
+use anchor_lang::prelude::*;
+use anchor_spl::token::{Mint, TokenAccount};
+use pyth_solana_receiver_sdk::price_update::PriceUpdateV2;
+
+#[derive(Accounts)]
+pub struct ExecuteStreamProposal<'info> {
+    #[account(mut)]
+    pub executor: Signer<'info>,
+
+    #[account(
+        seeds = [VAULT_SEED, vault_config.creator.as_ref()],
+        bump = vault_config.bump,
+        constraint = vault_config.version == VAULT_VERSION @ VaultError::AccountNotMigrated,
+        constraint = !vault_config.paused @ VaultError::VaultPaused,
+    )]
+    pub vault_config: Account<'info, VaultConfig>,
+
+    #[account(
+        mut,
+        seeds = [PROPOSAL_SEED, vault_config.key().as_ref(), proposal.proposal_id.to_le_bytes().as_ref()],
+        bump = proposal.bump,
+        constraint = proposal.version == PROPOSAL_VERSION @ VaultError::AccountNotMigrated,
+        constraint = proposal.vault == vault_config.key() @ VaultError::UnauthorizedSigner,
+    )]
+    pub proposal: Account<'info, Proposal>,
+
+    #[account(
+        init,
+        payer = executor,
+        space = 8 + Stream::INIT_SPACE,
+        seeds = [STREAM_SEED, proposal.key().as_ref()],
+        bump,
+    )]
+    pub stream: Account<'info, Stream>,
+
+    pub mint: Account<'info, Mint>,
+
+    #[account(
+        associated_token::mint = mint,
+        associated_token::authority = vault_config,
+    )]
+    pub vault_ata: Account<'info, TokenAccount>,
+
+    #[account(
+        init_if_needed,
+        payer = executor,
+        space = 8 + StreamReserve::INIT_SPACE,
+        seeds = [STREAM_RESERVE_SEED, vault_config.key().as_ref(), mint.key().as_ref()],
+        bump,
+    )]
+    pub stream_reserve: Account<'info, StreamReserve>,
+
+    /// Optional Pyth price update account, validated in handler
+    pub price_update: Option<Account<'info, PriceUpdateV2>>,
+
+    pub system_program: Program<'info, System>,
+    /// CHECK: must match vault_config.policy_program, validated in handler
+    pub policy_program: Option<UncheckedAccount<'info>>,
+}
+
+pub fn handler(ctx: Context<ExecuteStreamProposal>) -> Result<()> {
+    let proposal = &mut ctx.accounts.proposal;
+    let vault_config = &ctx.accounts.vault_config;
+
+    proposal.require_executable()?;
+    require!(
+        vault_config.signers.contains(&ctx.accounts.executor.key()),
+        VaultError::UnauthorizedSigner
+    );
+
+    let TransferType::Stream {
+        mint,
+        start_ts,
+        cliff_ts,
+        end_ts,
+    } = proposal.transfer_type
+    else {
+        return err!(VaultError::InvalidTransferType);
+    };
+    require_keys_eq!(
+        ctx.accounts.mint.key(),
+        mint,
+        VaultError::InvalidTransferType
+    );
+
+    // Pyth price gate
+    if let Some(ref condition) = proposal.price_condition {
+        condition.verify(ctx.accounts.price_update.as_ref())?;
+    }
+
+    let balance = ctx.accounts.vault_ata.amount;
+    let stream_info = ctx.accounts.stream.to_account_info();
+    run_policy_check(
+        vault_config,
+        proposal,
+        &stream_info,
+        ctx.accounts.policy_program.as_ref(),
+        PolicyStage::PreExecution,
+        proposal.amount,
+        balance,
+    )?;
+
+    // Funds stay in the vault ATA, reserved so token proposals can't spend
+    // them; the recipient draws them down via withdraw_stream.
+    let reserve = &mut ctx.accounts.stream_reserve;
+    require!(
+        ctx.accounts
+            .vault_ata
+            .amount
+            .saturating_sub(reserve.reserved)
+            >= proposal.amount,
+        VaultError::InsufficientBalance
+    );
+    reserve.vault = vault_config.key();
+    reserve.mint = mint;
+    reserve.bump = ctx.bumps.stream_reserve;
+    reserve.reserve(proposal.amount)?;
+
+    let stream = &mut ctx.accounts.stream;
+    stream.vault = vault_config.key();
+    stream.proposal = proposal.key();
+    stream.recipient = proposal.recipient;
+    stream.mint = mint;
+    stream.total_amount = proposal.amount;
+    stream.withdrawn_amount = 0;
+    stream.start_ts = start_ts;
+    stream.cliff_ts = cliff_ts;
+    stream.end_ts = end_ts;
+    stream.cancelled_at = None;
+    stream.bump = ctx.bumps.stream;
+
+    proposal.status = ProposalStatus::Executed;
+
+    run_policy_check(
+        vault_config,
+        proposal,
+        &stream_info,
+        ctx.accounts.policy_program.as_ref(),
+        PolicyStage::PostExecution,
+        proposal.amount,
+        balance,
+    )?;
+
+    Ok(())
+}
@@ -2,7 +2,7 @@ use crate::constants::*;
 use crate::error::VaultError;
 use crate::state::*;
 use anchor_lang::prelude::*;
-use anchor_spl::associated_token::AssociatedToken;
+use anchor_spl::associated_token::{get_associated_token_address, AssociatedToken};
 use anchor_spl::token::{transfer, Mint, Token, TokenAccount, Transfer};
 use pyth_solana_receiver_sdk::price_update::PriceUpdateV2;
 
@@ -15,6 +15,8 @@ pub struct ExecuteTokenProposal<'info> {
         mut,
         seeds = [VAULT_SEED, vault_config.creator.as_ref()],
         bump = vault_config.bump,
+        constraint = vault_config.version == VAULT_VERSION @ VaultError::AccountNotMigrated,
+        constraint = !vault_config.paused @ VaultError::VaultPaused,
     )]
     pub vault_config: Account<'info, VaultConfig>,
 
@@ -22,6 +24,7 @@ pub struct ExecuteTokenProposal<'info> {
         mut,
         seeds = [PROPOSAL_SEED, vault_config.key().as_ref(), proposal.proposal_id.to_le_bytes().as_ref()],
         bump = proposal.bump,
+        constraint = proposal.version == PROPOSAL_VERSION @ VaultError::AccountNotMigrated,
         constraint = proposal.vault == vault_config.key() @ VaultError::UnauthorizedSigner,
     )]
     pub proposal: Account<'info, Proposal>,
@@ -32,20 +35,37 @@ pub struct ExecuteTokenProposal<'info> {
 
     pub mint: Account<'info, Mint>,
 
+    /// Any vault-owned token account for the mint, ATA or not
     #[account(
         mut,
-        associated_token::mint = mint,
-        associated_token::authority = vault_config,
+        token::mint = mint,
+        token::authority = vault_config,
+    )]
+    pub vault_token_account: Account<'info, TokenAccount>,
+
+    /// CHECK: PDA checked by seeds; may not exist if the mint was never streamed
+    #[account(
+        seeds = [STREAM_RESERVE_SEED, vault_config.key().as_ref(), mint.key().as_ref()],
+        bump,
     )]
-    pub vault_ata: Account<'info, TokenAccount>,
+    pub stream_reserve: UncheckedAccount<'info>,
 
+    /// Recipient ATA, created if needed. Pass either this or `recipient_token_account`.
     #[account(
         init_if_needed,
         payer = executor,
         associated_token::mint = mint,
         associated_token::authority = recipient,
     )]
-    pub recipient_ata: Account<'info, TokenAccount>,
+    pub recipient_ata: Option<Account<'info, TokenAccount>>,
+
+    /// Existing recipient-owned token account, e.g. an exchange deposit account
+    #[account(
+        mut,
+        token::mint = mint,
+        token::authority = recipient,
+    )]
+    pub recipient_token_account: Option<Account<'info, TokenAccount>>,
 
     pub token_program: Program<'info, Token>,
     pub associated_token_program: Program<'info, AssociatedToken>,
@@ -53,15 +73,21 @@ pub struct ExecuteTokenProposal<'info> {
 
     /// Optional Pyth price update account, validated in handler
     pub price_update: Option<Account<'info, PriceUpdateV2>>,
+    /// CHECK: must match vault_config.policy_program, validated in handler
+    pub policy_program: Option<UncheckedAccount<'info>>,
 }
 
 pub fn handler(ctx: Context<ExecuteTokenProposal>) -> Result<()> {
     let proposal = &mut ctx.accounts.proposal;
     let vault_config = &ctx.accounts.vault_config;
 
+    proposal.require_executable()?;
     require!(
-        proposal.status == ProposalStatus::Approved,
-        VaultError::ProposalNotActive
+        proposal.transfer_type
+            == TransferType::SplToken {
+                mint: ctx.accounts.mint.key()
+            },
+        VaultError::InvalidTransferType
     );
     require!(
         vault_config.signers.contains(&ctx.accounts.executor.key()),
@@ -74,28 +100,38 @@ pub fn handler(ctx: Context<ExecuteTokenProposal>) -> Result<()> {
 
     // Pyth price gate
     if let Some(ref condition) = proposal.price_condition {
-        let price_update = ctx
-            .accounts
-            .price_update
-            .as_ref()
-            .ok_or(error!(VaultError::PriceConditionNotMet))?;
-
-        let price = price_update
-            .get_price_no_older_than(&Clock::get()?, condition.max_age_secs, &condition.feed_id)
-            .map_err(|_| error!(VaultError::StalePriceFeed))?;
-
-        if let Some(min_price) = condition.min_price {
-            require!(price.price >= min_price, VaultError::PriceConditionNotMet);
-        }
-        if let Some(max_price) = condition.max_price {
-            require!(price.price <= max_price, VaultError::PriceConditionNotMet);
-        }
+        condition.verify(ctx.accounts.price_update.as_ref())?;
     }
 
-    require!(
-        ctx.accounts.vault_ata.amount >= proposal.amount,
-        VaultError::InsufficientBalance
-    );
+    // Stream funds live in the vault ATA and can't be spent here.
+    let balance = ctx.accounts.vault_token_account.amount;
+    let reserved = if ctx.accounts.vault_token_account.key()
+        == get_associated_token_address(&vault_config.key(), &ctx.accounts.mint.key())
+    {
+        StreamReserve::reserved_in(&ctx.accounts.stream_reserve)?
+    } else {
+        0
+    };
+    let payment = proposal.next_payment(balance.saturating_sub(reserved))?;
+
+    let destination = match (
+        &ctx.accounts.recipient_ata,
+        &ctx.accounts.recipient_token_account,
+    ) {
+        (Some(ata), None) => ata.to_account_info(),
+        (None, Some(token_account)) => token_account.to_account_info(),
+        _ => return err!(VaultError::InvalidRecipientTokenAccount),
+    };
+
+    run_policy_check(
+        vault_config,
+        proposal,
+        &destination,
+        ctx.accounts.policy_program.as_ref(),
+        PolicyStage::PreExecution,
+        payment,
+        balance,
+    )?;
 
     // PDA signer seeds for the vault
     let creator = vault_config.creator;
@@ -106,16 +142,26 @@ pub fn handler(ctx: Context<ExecuteTokenProposal>) -> Result<()> {
         CpiContext::new_with_signer(
             ctx.accounts.token_program.to_account_info(),
             Transfer {
-                from: ctx.accounts.vault_ata.to_account_info(),
-                to: ctx.accounts.recipient_ata.to_account_info(),
+                from: ctx.accounts.vault_token_account.to_account_info(),
+                to: destination.clone(),
                 authority: vault_config.to_account_info(),
             },
             signer_seeds,
         ),
-        proposal.amount,
+        payment,
     )?;
 
-    proposal.status = ProposalStatus::Executed;
+    proposal.record_payment(payment);
+
+    run_policy_check(
+        vault_config,
+        proposal,
+        &destination,
+        ctx.accounts.policy_program.as_ref(),
+        PolicyStage::PostExecution,
+        payment,
+        balance - payment,
+    )?;
 
     Ok(())
 }
@@ -0,0 +1,141 @@
+use crate::constants::*;
+use crate::error::VaultError;
+use crate::state::*;
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::program::invoke;
+use anchor_spl::associated_token::AssociatedToken;
+use anchor_spl::token::spl_token::{self, native_mint};
+use anchor_spl::token::{close_account, CloseAccount, Mint, Token, TokenAccount};
+
+#[derive(Accounts)]
+pub struct ExecuteWrapProposal<'info> {
+    #[account(mut)]
+    pub executor: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [VAULT_SEED, vault_config.creator.as_ref()],
+        bump = vault_config.bump,
+        constraint = vault_config.version == VAULT_VERSION @ VaultError::AccountNotMigrated,
+        constraint = !vault_config.paused @ VaultError::VaultPaused,
+    )]
+    pub vault_config: Account<'info, VaultConfig>,
+
+    #[account(
+        mut,
+        seeds = [PROPOSAL_SEED, vault_config.key().as_ref(), proposal.proposal_id.to_le_bytes().as_ref()],
+        bump = proposal.bump,
+        constraint = proposal.version == PROPOSAL_VERSION @ VaultError::AccountNotMigrated,
+        constraint = proposal.vault == vault_config.key() @ VaultError::UnauthorizedSigner,
+    )]
+    pub proposal: Account<'info, Proposal>,
+
+    #[account(address = native_mint::ID)]
+    pub native_mint: Account<'info, Mint>,
+
+    #[account(
+        init_if_needed,
+        payer = executor,
+        associated_token::mint = native_mint,
+        associated_token::authority = vault_config,
+    )]
+    pub vault_wsol_ata: Account<'info, TokenAccount>,
+
+    /// CHECK: PDA checked by seeds; may not exist if wSOL was never streamed
+    #[account(
+        seeds = [STREAM_RESERVE_SEED, vault_config.key().as_ref(), native_mint.key().as_ref()],
+        bump,
+    )]
+    pub stream_reserve: UncheckedAccount<'info>,
+
+    pub token_program: Program<'info, Token>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+    /// CHECK: must match vault_config.policy_program, validated in handler
+    pub policy_program: Option<UncheckedAccount<'info>>,
+}
+
+pub fn handler(ctx: Context<ExecuteWrapProposal>) -> Result<()> {
+    let proposal = &mut ctx.accounts.proposal;
+    let vault_config = &ctx.accounts.vault_config;
+
+    proposal.require_executable()?;
+    require!(
+        vault_config.signers.contains(&ctx.accounts.executor.key()),
+        VaultError::UnauthorizedSigner
+    );
+
+    let vault_info = vault_config.to_account_info();
+    let wsol_info = ctx.accounts.vault_wsol_ata.to_account_info();
+
+    run_policy_check(
+        vault_config,
+        proposal,
+        &wsol_info,
+        ctx.accounts.policy_program.as_ref(),
+        PolicyStage::PreExecution,
+        proposal.amount,
+        vault_config.spendable_lamports(&vault_info)?,
+    )?;
+
+    match proposal.transfer_type {
+        TransferType::WrapSol => {
+            let available = vault_config.spendable_lamports(&vault_info)?;
+            require!(
+                available >= proposal.amount,
+                VaultError::InsufficientBalance
+            );
+
+            **vault_info.try_borrow_mut_lamports()? -= proposal.amount;
+            **wsol_info.try_borrow_mut_lamports()? += proposal.amount;
+
+            // The runtime checks lamport balances across a CPI using only the
+            // accounts passed to it, so the debited vault rides along as an
+            // extra account that sync_native ignores.
+            let mut sync_ix =
+                spl_token::instruction::sync_native(&spl_token::ID, &wsol_info.key())?;
+            sync_ix
+                .accounts
+                .push(AccountMeta::new(vault_info.key(), false));
+            invoke(&sync_ix, &[wsol_info.clone(), vault_info.clone()])?;
+        }
+        TransferType::UnwrapSol => {
+            // Closing the ATA would hand wSOL owed to streams back to the
+            // vault as spendable lamports.
+            require!(
+                StreamReserve::reserved_in(&ctx.accounts.stream_reserve)? == 0,
+                VaultError::StreamFundsReserved
+            );
+
+            // PDA signer seeds for the vault
+            let creator = vault_config.creator;
+            let bump = [vault_config.bump];
+            let signer_seeds: &[&[&[u8]]] = &[&[VAULT_SEED, creator.as_ref(), &bump]];
+
+            close_account(CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                CloseAccount {
+                    account: wsol_info.clone(),
+                    destination: vault_info.clone(),
+                    authority: vault_info.clone(),
+                },
+                signer_seeds,
+            ))?;
+        }
+        _ => return err!(VaultError::InvalidTransferType),
+    }
+
+    proposal.status = ProposalStatus::Executed;
+
+    run_policy_check(
+        vault_config,
+        proposal,
+        &wsol_info,
+        ctx.accounts.policy_program.as_ref(),
+        PolicyStage::PostExecution,
+        proposal.amount,
+        vault_config.spendable_lamports(&vault_info)?,
+    )?;
+
+    Ok(())
+}
@@ -0,0 +1,49 @@
+use crate::constants::*;
+use crate::error::VaultError;
+use crate::state::*;
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+pub struct FinalizeProposal<'info> {
+    #[account(
+        seeds = [VAULT_SEED, vault_config.creator.as_ref()],
+        bump = vault_config.bump,
+        constraint = vault_config.version == VAULT_VERSION @ VaultError::AccountNotMigrated,
+    )]
+    pub vault_config: Account<'info, VaultConfig>,
+
+    #[account(
+        mut,
+        seeds = [PROPOSAL_SEED, vault_config.key().as_ref(), proposal.proposal_id.to_le_bytes().as_ref()],
+        bump = proposal.bump,
+        constraint = proposal.version == PROPOSAL_VERSION @ VaultError::AccountNotMigrated,
+        constraint = proposal.vault == vault_config.key() @ VaultError::UnauthorizedSigner,
+    )]
+    pub proposal: Account<'info, Proposal>,
+}
+
+/// Permissionless crank resolving an active proposal once its voting period
+/// has ended. Stale proposals can never collect more votes, so they are
+/// rejected right away.
+pub fn handler(ctx: Context<FinalizeProposal>) -> Result<()> {
+    let vault = &ctx.accounts.vault_config;
+    let proposal = &mut ctx.accounts.proposal;
+
+    require!(
+        proposal.status == ProposalStatus::Active,
+        VaultError::ProposalNotActive
+    );
+    if proposal.is_stale(vault) {
+        proposal.status = ProposalStatus::Rejected;
+        return Ok(());
+    }
+    let voting_ends_at = proposal
+        .voting_ends_at
+        .ok_or(error!(VaultError::VotingStillOpen))?;
+    require!(
+        Clock::get()?.unix_timestamp >= voting_ends_at,
+        VaultError::VotingStillOpen
+    );
+
+    proposal.resolve_after_deadline(vault)
+}
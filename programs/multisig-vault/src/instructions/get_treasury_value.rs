@@ -0,0 +1,158 @@
+use crate::constants::*;
+use crate::error::VaultError;
+use crate::state::*;
+use anchor_lang::prelude::*;
+use anchor_spl::associated_token::get_associated_token_address;
+use anchor_spl::token::TokenAccount;
+use pyth_solana_receiver_sdk::price_update::PriceUpdateV2;
+
+#[derive(Accounts)]
+pub struct GetTreasuryValue<'info> {
+    #[account(
+        seeds = [VAULT_SEED, vault_config.creator.as_ref()],
+        bump = vault_config.bump,
+        constraint = vault_config.version == VAULT_VERSION @ VaultError::AccountNotMigrated,
+    )]
+    pub vault_config: Account<'info, VaultConfig>,
+
+    #[account(
+        seeds = [ASSET_REGISTRY_SEED, vault_config.key().as_ref()],
+        bump = asset_registry.bump,
+    )]
+    pub asset_registry: Account<'info, AssetRegistry>,
+
+    /// SOL/USD price update, required when the registry has a SOL feed
+    pub sol_price_update: Option<Account<'info, PriceUpdateV2>>,
+}
+
+#[event]
+pub struct TreasuryValued {
+    pub vault: Pubkey,
+    /// Total value in micro-USD
+    pub value: u64,
+    pub timestamp: i64,
+}
+
+/// Values the vault's SOL and the token accounts passed in `remaining_accounts`
+/// as `[vault_ata, price_update]` pairs. Every registry entry with a price feed
+/// needs exactly one pair, so no holding can be left out. Returns micro-USD.
+pub fn handler<'info>(
+    ctx: Context<'_, '_, 'info, 'info, GetTreasuryValue<'info>>,
+    max_age_secs: u64,
+) -> Result<u64> {
+    let clock = Clock::get()?;
+    let vault_key = ctx.accounts.vault_config.key();
+    let registry = &ctx.accounts.asset_registry;
+    let mut total: u64 = 0;
+
+    if let Some(feed_id) = registry.sol_price_feed_id {
+        let price_update = ctx
+            .accounts
+            .sol_price_update
+            .as_ref()
+            .ok_or(error!(VaultError::MissingPriceFeed))?;
+        let lamports =
+            VaultConfig::available_lamports(&ctx.accounts.vault_config.to_account_info())?;
+        let value = usd_value(
+            lamports,
+            SOL_DECIMALS,
+            price_update,
+            &feed_id,
+            max_age_secs,
+            &clock,
+        )?;
+        total = total
+            .checked_add(value)
+            .ok_or(error!(VaultError::ValuationOverflow))?;
+    }
+
+    require!(
+        ctx.remaining_accounts.len() % 2 == 0,
+        VaultError::InvalidValuationAccounts
+    );
+
+    let mut valued: Vec<Pubkey> = Vec::new();
+    for pair in ctx.remaining_accounts.chunks(2) {
+        let token_account = Account::<TokenAccount>::try_from(&pair[0])?;
+        require_keys_eq!(
+            pair[0].key(),
+            get_associated_token_address(&vault_key, &token_account.mint),
+            VaultError::InvalidValuationAccounts
+        );
+        // Each mint counts once
+        require!(
+            !valued.contains(&token_account.mint),
+            VaultError::InvalidValuationAccounts
+        );
+        valued.push(token_account.mint);
+
+        let asset = registry
+            .assets
+            .iter()
+            .find(|a| a.mint == token_account.mint)
+            .ok_or(error!(VaultError::AssetNotTracked))?;
+        let feed_id = asset
+            .price_feed_id
+            .ok_or(error!(VaultError::MissingPriceFeed))?;
+        let price_update = Account::<PriceUpdateV2>::try_from(&pair[1])?;
+
+        let value = usd_value(
+            token_account.amount,
+            asset.decimals,
+            &price_update,
+            &feed_id,
+            max_age_secs,
+            &clock,
+        )?;
+        total = total
+            .checked_add(value)
+            .ok_or(error!(VaultError::ValuationOverflow))?;
+    }
+
+    let priced = registry
+        .assets
+        .iter()
+        .filter(|a| a.price_feed_id.is_some())
+        .count();
+    require!(valued.len() == priced, VaultError::InvalidValuationAccounts);
+
+    emit!(TreasuryValued {
+        vault: vault_key,
+        value: total,
+        timestamp: clock.unix_timestamp,
+    });
+
+    Ok(total)
+}
+
+/// Micro-USD value of `amount` base units of an asset with `decimals`.
+fn usd_value(
+    amount: u64,
+    decimals: u8,
+    price_update: &PriceUpdateV2,
+    feed_id: &[u8; 32],
+    max_age_secs: u64,
+    clock: &Clock,
+) -> Result<u64> {
+    let price = price_update
+        .get_price_no_older_than(clock, max_age_secs, feed_id)
+        .map_err(|_| error!(VaultError::StalePriceFeed))?;
+    let unit_price = u128::try_from(price.price).map_err(|_| error!(VaultError::InvalidPrice))?;
+
+    let raw = (amount as u128)
+        .checked_mul(unit_price)
+        .ok_or(error!(VaultError::ValuationOverflow))?;
+    let exponent = price.exponent + USD_DECIMALS as i32 - decimals as i32;
+    let value = if exponent >= 0 {
+        10u128
+            .checked_pow(exponent as u32)
+            .and_then(|scale| raw.checked_mul(scale))
+            .ok_or(error!(VaultError::ValuationOverflow))?
+    } else {
+        10u128
+            .checked_pow(exponent.unsigned_abs())
+            .map_or(0, |scale| raw / scale)
+    };
+
+    u64::try_from(value).map_err(|_| error!(VaultError::ValuationOverflow))
+}
@@ -1,9 +1,9 @@
 use crate::constants::*;
-use crate::error::VaultError;
-use crate::state::VaultConfig;
+use crate::state::{DeadlineRule, DepositPolicy, RecipientPolicy, VaultConfig};
 use anchor_lang::prelude::*;
 
 #[derive(Accounts)]
+#[instruction(signers: Vec<Pubkey>)]
 pub struct Initialize<'info> {
     #[account(mut)]
     pub creator: Signer<'info>,
@@ -11,7 +11,7 @@ pub struct Initialize<'info> {
     #[account(
         init,
         payer = creator,
-        space = 8 + VaultConfig::INIT_SPACE,
+        space = VaultConfig::space(signers.len()),
         seeds = [VAULT_SEED, creator.key().as_ref()],
         bump,
     )]
@@ -20,25 +20,31 @@ pub struct Initialize<'info> {
 }
 
 pub fn handler(ctx: Context<Initialize>, signers: Vec<Pubkey>, threshold: u8) -> Result<()> {
-    require!(
-        !signers.is_empty() && threshold >= 1 && threshold <= signers.len() as u8,
-        VaultError::InvalidThreshold
-    );
-    require!(signers.len() <= MAX_SIGNERS, VaultError::TooManySigners);
-
-    let mut sorted = signers.clone();
-    sorted.sort();
-
-    for i in 1..sorted.len() {
-        require!(sorted[i] != sorted[i - 1], VaultError::DuplicateSigner);
-    }
+    VaultConfig::validate_signers(&signers, threshold)?;
 
     let vault = &mut ctx.accounts.vault_config;
     vault.creator = ctx.accounts.creator.key();
     vault.signers = signers;
     vault.threshold = threshold;
+    vault.quorum = 0;
+    vault.cancel_threshold = threshold;
     vault.proposal_count = 0;
     vault.bump = ctx.bumps.vault_config;
+    vault.version = VAULT_VERSION;
+    vault.config_epoch = 0;
+    vault.recipient_policy = RecipientPolicy::Open;
+    vault.address_book = vec![];
+    vault.threshold_tiers = vec![];
+    vault.paused = false;
+    vault.guardian = None;
+    vault.veto_council = vec![];
+    vault.timelock_secs = 0;
+    vault.voting_period_secs = 0;
+    vault.deadline_rule = DeadlineRule::Reject;
+    vault.recovery = None;
+    vault.share_pool = None;
+    vault.deposit_policy = DepositPolicy::default();
+    vault.policy_program = None;
 
     Ok(())
 }
@@ -0,0 +1,65 @@
+use crate::constants::*;
+use crate::error::VaultError;
+use crate::state::*;
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+#[instruction(new_signers: Vec<Pubkey>)]
+pub struct InitiateRecovery<'info> {
+    #[account(mut)]
+    pub initiator: Signer<'info>,
+
+    #[account(
+        seeds = [VAULT_SEED, vault_config.creator.as_ref()],
+        bump = vault_config.bump,
+        constraint = vault_config.version == VAULT_VERSION @ VaultError::AccountNotMigrated,
+    )]
+    pub vault_config: Account<'info, VaultConfig>,
+
+    #[account(
+        init,
+        payer = initiator,
+        space = RecoveryRequest::space(new_signers.len()),
+        seeds = [RECOVERY_SEED, vault_config.key().as_ref()],
+        bump,
+    )]
+    pub recovery_request: Account<'info, RecoveryRequest>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler(
+    ctx: Context<InitiateRecovery>,
+    new_signers: Vec<Pubkey>,
+    new_threshold: u8,
+) -> Result<()> {
+    let vault = &ctx.accounts.vault_config;
+    let recovery = vault
+        .recovery
+        .as_ref()
+        .ok_or(error!(VaultError::RecoveryNotConfigured))?;
+    let initiator = ctx.accounts.initiator.key();
+
+    require!(
+        recovery.keys.contains(&initiator),
+        VaultError::NotRecoveryKey
+    );
+    VaultConfig::validate_signers(&new_signers, new_threshold)?;
+
+    let now = Clock::get()?.unix_timestamp;
+    let request = &mut ctx.accounts.recovery_request;
+    request.vault = vault.key();
+    request.initiator = initiator;
+    request.new_signers = new_signers;
+    request.new_threshold = new_threshold;
+    request.approvals = vec![initiator];
+    request.cancel_votes = Vec::new();
+    request.initiated_at = now;
+    request.executable_at = None;
+    request.approved_at = None;
+    request.bump = ctx.bumps.recovery_request;
+
+    request.refresh(recovery.threshold, recovery.delay_secs, now)?;
+
+    Ok(())
+}
@@ -0,0 +1,115 @@
+use crate::constants::*;
+use crate::error::VaultError;
+use crate::instructions::migrate_vault::resize_account;
+use crate::state::*;
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+pub struct MigrateProposal<'info> {
+    /// Pays the rent for the larger account
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    /// Must already be migrated; supplies the threshold snapshot
+    #[account(
+        seeds = [VAULT_SEED, vault_config.creator.as_ref()],
+        bump = vault_config.bump,
+        constraint = vault_config.version == VAULT_VERSION @ VaultError::AccountNotMigrated,
+    )]
+    pub vault_config: Account<'info, VaultConfig>,
+
+    /// CHECK: Legacy layout, deserialized and validated in handler
+    #[account(mut, owner = crate::ID)]
+    pub proposal: UncheckedAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Upgrades an unversioned `Proposal` in place, snapshotting the vault's
+/// current threshold and leaving it without a time lock.
+pub fn handler(ctx: Context<MigrateProposal>) -> Result<()> {
+    let info = ctx.accounts.proposal.to_account_info();
+    let vault = &ctx.accounts.vault_config;
+    let vault_key = vault.key();
+
+    let legacy = {
+        let data = info.try_borrow_data()?;
+        require!(
+            data.starts_with(Proposal::DISCRIMINATOR),
+            VaultError::InvalidLegacyAccount
+        );
+        require!(
+            data.len() == 8 + LegacyProposal::INIT_SPACE,
+            VaultError::AlreadyMigrated
+        );
+        LegacyProposal::deserialize(&mut &data[8..])?
+    };
+
+    require_keys_eq!(legacy.vault, vault_key, VaultError::InvalidLegacyAccount);
+    let expected = Pubkey::create_program_address(
+        &[
+            PROPOSAL_SEED,
+            vault_key.as_ref(),
+            legacy.proposal_id.to_le_bytes().as_ref(),
+            &[legacy.bump],
+        ],
+        &crate::ID,
+    )
+    .map_err(|_| error!(VaultError::InvalidLegacyAccount))?;
+    require_keys_eq!(info.key(), expected, VaultError::InvalidLegacyAccount);
+
+    let status: ProposalStatus = legacy.status.into();
+    let amount_paid = if status == ProposalStatus::Executed {
+        legacy.amount
+    } else {
+        0
+    };
+
+    let proposal = Proposal {
+        version: PROPOSAL_VERSION,
+        vault: legacy.vault,
+        proposal_id: legacy.proposal_id,
+        proposer: legacy.proposer,
+        recipient: legacy.recipient,
+        amount: legacy.amount,
+        allow_partial: false,
+        amount_paid,
+        transfer_type: legacy.transfer_type.into(),
+        description: legacy.description,
+        memo: None,
+        price_condition: legacy.price_condition,
+        approval_bitmap: vote_bitmap(&legacy.approvals, vault),
+        rejection_bitmap: vote_bitmap(&legacy.rejections, vault),
+        abstain_bitmap: 0,
+        config_epoch: vault.config_epoch,
+        cancel_bitmap: 0,
+        cancel_epoch: vault.config_epoch,
+        status,
+        bump: legacy.bump,
+        threshold: vault.threshold,
+        quorum: vault.quorum,
+        executable_at: 0,
+        voting_ends_at: None,
+        deadline_rule: DeadlineRule::Reject,
+        amendment_count: 0,
+    };
+
+    resize_account(
+        &info,
+        Proposal::space(&proposal.description, None),
+        &ctx.accounts.payer,
+        &ctx.accounts.system_program,
+    )?;
+    proposal.try_serialize(&mut &mut info.try_borrow_mut_data()?[..])?;
+
+    Ok(())
+}
+
+/// Legacy votes as a bitmap over the vault's signers. Voters who are no longer
+/// signers are dropped.
+fn vote_bitmap(voters: &[Pubkey], vault: &VaultConfig) -> u64 {
+    voters
+        .iter()
+        .filter_map(|voter| vault.signer_index(voter))
+        .fold(0, |bitmap, index| bitmap | 1 << index)
+}
@@ -0,0 +1,111 @@
+use crate::constants::*;
+use crate::error::VaultError;
+use crate::state::*;
+use anchor_lang::prelude::*;
+use anchor_lang::system_program;
+
+#[derive(Accounts)]
+pub struct MigrateVault<'info> {
+    /// Pays the rent for the larger account
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    /// CHECK: Legacy layout, deserialized and validated in handler
+    #[account(mut, owner = crate::ID)]
+    pub vault_config: UncheckedAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Upgrades an unversioned `VaultConfig` in place. New settings start at the
+/// same defaults `initialize` uses.
+pub fn handler(ctx: Context<MigrateVault>) -> Result<()> {
+    let info = ctx.accounts.vault_config.to_account_info();
+
+    let legacy = {
+        let data = info.try_borrow_data()?;
+        require!(
+            data.starts_with(VaultConfig::DISCRIMINATOR),
+            VaultError::InvalidLegacyAccount
+        );
+        require!(
+            data.len() == 8 + LegacyVaultConfig::INIT_SPACE,
+            VaultError::AlreadyMigrated
+        );
+        LegacyVaultConfig::deserialize(&mut &data[8..])?
+    };
+
+    let expected = Pubkey::create_program_address(
+        &[VAULT_SEED, legacy.creator.as_ref(), &[legacy.bump]],
+        &crate::ID,
+    )
+    .map_err(|_| error!(VaultError::InvalidLegacyAccount))?;
+    require_keys_eq!(info.key(), expected, VaultError::InvalidLegacyAccount);
+
+    let vault = VaultConfig {
+        version: VAULT_VERSION,
+        creator: legacy.creator,
+        signers: legacy.signers,
+        threshold: legacy.threshold,
+        quorum: 0,
+        cancel_threshold: legacy.threshold,
+        proposal_count: legacy.proposal_count,
+        bump: legacy.bump,
+        config_epoch: 0,
+        recipient_policy: RecipientPolicy::Open,
+        address_book: vec![],
+        threshold_tiers: vec![],
+        paused: false,
+        guardian: None,
+        veto_council: vec![],
+        timelock_secs: 0,
+        voting_period_secs: 0,
+        deadline_rule: DeadlineRule::Reject,
+        recovery: None,
+        share_pool: None,
+        deposit_policy: DepositPolicy::default(),
+        policy_program: None,
+    };
+
+    resize_account(
+        &info,
+        VaultConfig::space(vault.signers.len()),
+        &ctx.accounts.payer,
+        &ctx.accounts.system_program,
+    )?;
+    vault.try_serialize(&mut &mut info.try_borrow_mut_data()?[..])?;
+
+    Ok(())
+}
+
+/// Resizes `info` to `space`. When growing, `payer` covers the extra rent so
+/// the account's own lamports are untouched; rent freed by shrinking stays in
+/// the account.
+pub fn resize_account<'info>(
+    info: &AccountInfo<'info>,
+    space: usize,
+    payer: &Signer<'info>,
+    system_program: &Program<'info, System>,
+) -> Result<()> {
+    let rent = Rent::get()?;
+    let extra_rent = rent
+        .minimum_balance(space)
+        .saturating_sub(rent.minimum_balance(info.data_len()));
+
+    if extra_rent > 0 {
+        system_program::transfer(
+            CpiContext::new(
+                system_program.to_account_info(),
+                system_program::Transfer {
+                    from: payer.to_account_info(),
+                    to: info.clone(),
+                },
+            ),
+            extra_rent,
+        )?;
+    }
+
+    info.resize(space)?;
+
+    Ok(())
+}
@@ -1,20 +1,72 @@
+pub mod abstain_proposal;
+pub mod amend_proposal;
 pub mod approve_proposal;
+pub mod approve_recovery;
 pub mod cancel_proposal;
+pub mod cancel_recovery;
+pub mod cancel_stream;
 pub mod create_proposal;
+pub mod deposit_for_shares;
 pub mod deposit_sol;
 pub mod deposit_token;
+pub mod execute_core_asset_proposal;
+pub mod execute_governance_proposal;
+pub mod execute_nft_proposal;
+pub mod execute_recovery;
+pub mod execute_redemption_proposal;
+pub mod execute_share_pool_proposal;
 pub mod execute_sol_proposal;
+pub mod execute_stake_proposal;
+pub mod execute_stream_proposal;
 pub mod execute_token_proposal;
+pub mod execute_wrap_proposal;
+pub mod finalize_proposal;
+pub mod get_treasury_value;
 pub mod initialize;
+pub mod initiate_recovery;
+pub mod migrate_proposal;
+pub mod migrate_vault;
+pub mod pause_vault;
+pub mod redeem_shares;
 pub mod reject_proposal;
+pub mod request_redemption;
+pub mod veto_proposal;
+pub mod vote_cancel_proposal;
+pub mod withdraw_stream;
 
 #[allow(ambiguous_glob_reexports)]
+pub use abstain_proposal::*;
+pub use amend_proposal::*;
 pub use approve_proposal::*;
+pub use approve_recovery::*;
 pub use cancel_proposal::*;
+pub use cancel_recovery::*;
+pub use cancel_stream::*;
 pub use create_proposal::*;
+pub use deposit_for_shares::*;
 pub use deposit_sol::*;
 pub use deposit_token::*;
+pub use execute_core_asset_proposal::*;
+pub use execute_governance_proposal::*;
+pub use execute_nft_proposal::*;
+pub use execute_recovery::*;
+pub use execute_redemption_proposal::*;
+pub use execute_share_pool_proposal::*;
 pub use execute_sol_proposal::*;
+pub use execute_stake_proposal::*;
+pub use execute_stream_proposal::*;
 pub use execute_token_proposal::*;
+pub use execute_wrap_proposal::*;
+pub use finalize_proposal::*;
+pub use get_treasury_value::*;
 pub use initialize::*;
+pub use initiate_recovery::*;
+pub use migrate_proposal::*;
+pub use migrate_vault::*;
+pub use pause_vault::*;
+pub use redeem_shares::*;
 pub use reject_proposal::*;
+pub use request_redemption::*;
+pub use veto_proposal::*;
+pub use vote_cancel_proposal::*;
+pub use withdraw_stream::*;
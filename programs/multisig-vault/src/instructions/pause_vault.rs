@@ -0,0 +1,31 @@
+use crate::constants::*;
+use crate::error::VaultError;
+use crate::state::VaultConfig;
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+pub struct PauseVault<'info> {
+    pub guardian: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [VAULT_SEED, vault_config.creator.as_ref()],
+        bump = vault_config.bump,
+        constraint = vault_config.version == VAULT_VERSION @ VaultError::AccountNotMigrated,
+    )]
+    pub vault_config: Account<'info, VaultConfig>,
+}
+
+pub fn handler(ctx: Context<PauseVault>) -> Result<()> {
+    let vault = &mut ctx.accounts.vault_config;
+
+    require!(
+        vault.guardian == Some(ctx.accounts.guardian.key()),
+        VaultError::NotGuardian
+    );
+
+    // Unpausing goes through a threshold-approved Unpause governance proposal.
+    vault.paused = true;
+
+    Ok(())
+}
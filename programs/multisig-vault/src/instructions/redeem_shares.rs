@@ -0,0 +1,121 @@
+use crate::constants::*;
+use crate::error::VaultError;
+use crate::state::*;
+use anchor_lang::prelude::*;
+use anchor_spl::token::{burn, Burn, Mint, Token, TokenAccount};
+
+#[derive(Accounts)]
+pub struct RedeemShares<'info> {
+    #[account(mut)]
+    pub holder: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [VAULT_SEED, vault_config.creator.as_ref()],
+        bump = vault_config.bump,
+        constraint = vault_config.version == VAULT_VERSION @ VaultError::AccountNotMigrated,
+        constraint = !vault_config.paused @ VaultError::VaultPaused,
+    )]
+    pub vault_config: Account<'info, VaultConfig>,
+
+    #[account(
+        mut,
+        seeds = [SHARE_MINT_SEED, vault_config.key().as_ref()],
+        bump,
+    )]
+    pub share_mint: Account<'info, Mint>,
+
+    #[account(
+        mut,
+        token::mint = share_mint,
+        token::authority = holder,
+    )]
+    pub holder_share_account: Account<'info, TokenAccount>,
+
+    pub token_program: Program<'info, Token>,
+    /// CHECK: must match vault_config.policy_program, validated in handler
+    pub policy_program: Option<UncheckedAccount<'info>>,
+}
+
+/// Redemption within the vault's auto-approval limit, paid out immediately.
+pub fn handler(ctx: Context<RedeemShares>, shares: u64) -> Result<()> {
+    let now = Clock::get()?.unix_timestamp;
+    ctx.accounts
+        .vault_config
+        .share_pool
+        .as_mut()
+        .ok_or(error!(VaultError::SharePoolNotInitialized))?
+        .record_auto_redemption(shares, now)?;
+
+    let vault_info = ctx.accounts.vault_config.to_account_info();
+    let holder_info = ctx.accounts.holder.to_account_info();
+    run_redemption_policy_check(
+        &ctx.accounts.vault_config,
+        &holder_info,
+        ctx.accounts.policy_program.as_ref(),
+        PolicyStage::PreExecution,
+        shares,
+        VaultConfig::available_lamports(&vault_info)?,
+    )?;
+
+    burn_and_pay(
+        &ctx.accounts.token_program,
+        &ctx.accounts.share_mint,
+        &ctx.accounts.holder_share_account,
+        &ctx.accounts.holder,
+        &mut ctx.accounts.vault_config,
+        shares,
+    )?;
+
+    run_redemption_policy_check(
+        &ctx.accounts.vault_config,
+        &holder_info,
+        ctx.accounts.policy_program.as_ref(),
+        PolicyStage::PostExecution,
+        shares,
+        VaultConfig::available_lamports(&vault_info)?,
+    )?;
+
+    Ok(())
+}
+
+/// Burns `shares` from the holder and pays their pro-rata slice of the pool's
+/// SOL. Returns the lamports paid.
+pub fn burn_and_pay<'info>(
+    token_program: &Program<'info, Token>,
+    share_mint: &Account<'info, Mint>,
+    holder_share_account: &Account<'info, TokenAccount>,
+    holder: &Signer<'info>,
+    vault_config: &mut Account<'info, VaultConfig>,
+    shares: u64,
+) -> Result<u64> {
+    let vault_info = vault_config.to_account_info();
+    let pool = vault_config
+        .share_pool
+        .as_mut()
+        .ok_or(error!(VaultError::SharePoolNotInitialized))?;
+    let lamports = SharePool::lamports_for_shares(shares, share_mint.supply, pool.pool_lamports);
+    require!(lamports > 0, VaultError::ZeroShares);
+    require!(
+        VaultConfig::available_lamports(&vault_info)? >= lamports,
+        VaultError::InsufficientBalance
+    );
+    pool.pool_lamports -= lamports;
+
+    burn(
+        CpiContext::new(
+            token_program.to_account_info(),
+            Burn {
+                mint: share_mint.to_account_info(),
+                from: holder_share_account.to_account_info(),
+                authority: holder.to_account_info(),
+            },
+        ),
+        shares,
+    )?;
+
+    **vault_info.try_borrow_mut_lamports()? -= lamports;
+    **holder.to_account_info().try_borrow_mut_lamports()? += lamports;
+
+    Ok(lamports)
+}
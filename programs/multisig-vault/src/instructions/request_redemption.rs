@@ -0,0 +1,92 @@
+use crate::constants::*;
+use crate::error::VaultError;
+use crate::state::*;
+use anchor_lang::prelude::*;
+use anchor_spl::token::{Mint, TokenAccount};
+
+#[derive(Accounts)]
+pub struct RequestRedemption<'info> {
+    #[account(mut)]
+    pub holder: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [VAULT_SEED, vault_config.creator.as_ref()],
+        bump = vault_config.bump,
+        constraint = vault_config.version == VAULT_VERSION @ VaultError::AccountNotMigrated,
+        constraint = !vault_config.paused @ VaultError::VaultPaused,
+    )]
+    pub vault_config: Account<'info, VaultConfig>,
+
+    #[account(
+        seeds = [SHARE_MINT_SEED, vault_config.key().as_ref()],
+        bump,
+    )]
+    pub share_mint: Account<'info, Mint>,
+
+    #[account(
+        token::mint = share_mint,
+        token::authority = holder,
+    )]
+    pub holder_share_account: Account<'info, TokenAccount>,
+
+    #[account(
+        init,
+        payer = holder,
+        space = Proposal::space("", None),
+        seeds = [PROPOSAL_SEED, vault_config.key().as_ref(), vault_config.proposal_count.to_le_bytes().as_ref()],
+        bump,
+    )]
+    pub proposal: Account<'info, Proposal>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Opens a redemption proposal for signers to vote on. Shares stay with the
+/// holder and are burned when the approved proposal is executed.
+pub fn handler(ctx: Context<RequestRedemption>, shares: u64) -> Result<()> {
+    let vault = &ctx.accounts.vault_config;
+
+    require!(
+        vault.share_pool.is_some(),
+        VaultError::SharePoolNotInitialized
+    );
+    require!(shares > 0, VaultError::ZeroShares);
+    require!(
+        ctx.accounts.holder_share_account.amount >= shares,
+        VaultError::InsufficientBalance
+    );
+
+    let holder = ctx.accounts.holder.key();
+    let proposal = &mut ctx.accounts.proposal;
+    proposal.vault = vault.key();
+    proposal.proposal_id = vault.proposal_count;
+    proposal.proposer = holder;
+    proposal.recipient = holder;
+    proposal.amount = shares;
+    proposal.allow_partial = false;
+    proposal.amount_paid = 0;
+    proposal.transfer_type = TransferType::RedeemShares;
+    proposal.description = String::new();
+    proposal.memo = None;
+    proposal.price_condition = None;
+    proposal.approval_bitmap = 0;
+    proposal.rejection_bitmap = 0;
+    proposal.abstain_bitmap = 0;
+    proposal.config_epoch = vault.config_epoch;
+    proposal.cancel_bitmap = 0;
+    proposal.cancel_epoch = vault.config_epoch;
+    proposal.status = ProposalStatus::Active;
+    proposal.bump = ctx.bumps.proposal;
+    proposal.version = PROPOSAL_VERSION;
+    proposal.threshold = vault.threshold;
+    proposal.quorum = vault.quorum;
+    proposal.executable_at = 0;
+    proposal.voting_ends_at = vault.voting_deadline(Clock::get()?.unix_timestamp)?;
+    proposal.deadline_rule = vault.deadline_rule;
+    proposal.amendment_count = 0;
+
+    ctx.accounts.vault_config.proposal_count += 1;
+
+    Ok(())
+}
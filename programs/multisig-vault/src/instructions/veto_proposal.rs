@@ -0,0 +1,47 @@
+use crate::constants::*;
+use crate::error::VaultError;
+use crate::state::*;
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+pub struct VetoProposal<'info> {
+    pub vetoer: Signer<'info>,
+
+    #[account(
+        seeds = [VAULT_SEED, vault_config.creator.as_ref()],
+        bump = vault_config.bump,
+        constraint = vault_config.version == VAULT_VERSION @ VaultError::AccountNotMigrated,
+    )]
+    pub vault_config: Account<'info, VaultConfig>,
+
+    #[account(
+        mut,
+        seeds = [PROPOSAL_SEED, vault_config.key().as_ref(), proposal.proposal_id.to_le_bytes().as_ref()],
+        bump = proposal.bump,
+        constraint = proposal.version == PROPOSAL_VERSION @ VaultError::AccountNotMigrated,
+        constraint = proposal.vault == vault_config.key() @ VaultError::UnauthorizedSigner,
+    )]
+    pub proposal: Account<'info, Proposal>,
+}
+
+pub fn handler(ctx: Context<VetoProposal>) -> Result<()> {
+    let vault = &ctx.accounts.vault_config;
+    let proposal = &mut ctx.accounts.proposal;
+
+    require!(
+        vault.veto_council.contains(&ctx.accounts.vetoer.key()),
+        VaultError::NotVetoMember
+    );
+    require!(
+        proposal.status == ProposalStatus::Approved,
+        VaultError::ProposalNotActive
+    );
+    require!(
+        Clock::get()?.unix_timestamp < proposal.executable_at,
+        VaultError::VetoWindowClosed
+    );
+
+    proposal.status = ProposalStatus::Vetoed;
+
+    Ok(())
+}
@@ -0,0 +1,47 @@
+use crate::constants::*;
+use crate::error::VaultError;
+use crate::state::*;
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+pub struct VoteCancelProposal<'info> {
+    pub signer: Signer<'info>,
+
+    #[account(
+        seeds = [VAULT_SEED, vault_config.creator.as_ref()],
+        bump = vault_config.bump,
+        constraint = vault_config.version == VAULT_VERSION @ VaultError::AccountNotMigrated,
+    )]
+    pub vault_config: Account<'info, VaultConfig>,
+
+    #[account(
+        mut,
+        seeds = [PROPOSAL_SEED, vault_config.key().as_ref(), proposal.proposal_id.to_le_bytes().as_ref()],
+        bump = proposal.bump,
+        constraint = proposal.version == PROPOSAL_VERSION @ VaultError::AccountNotMigrated,
+        constraint = proposal.vault == vault_config.key() @ VaultError::UnauthorizedSigner,
+    )]
+    pub proposal: Account<'info, Proposal>,
+}
+
+/// Votes to cancel a proposal that hasn't been fully executed. It is cancelled once the
+/// vault's cancel threshold is reached, regardless of who proposed it.
+pub fn handler(ctx: Context<VoteCancelProposal>) -> Result<()> {
+    let vault = &ctx.accounts.vault_config;
+    let proposal = &mut ctx.accounts.proposal;
+
+    require!(
+        matches!(
+            proposal.status,
+            ProposalStatus::Active | ProposalStatus::Approved | ProposalStatus::PartiallyExecuted
+        ),
+        VaultError::ProposalNotActive
+    );
+
+    let cancel_votes = proposal.record_cancel_vote(vault, &ctx.accounts.signer.key())?;
+    if cancel_votes >= vault.cancel_threshold as usize {
+        proposal.status = ProposalStatus::Cancelled;
+    }
+
+    Ok(())
+}
@@ -0,0 +1,118 @@
+use crate::constants::*;
+use crate::error::VaultError;
+use crate::state::*;
+use anchor_lang::prelude::*;
+use anchor_spl::associated_token::AssociatedToken;
+use anchor_spl::token::{transfer, Mint, Token, TokenAccount, Transfer};
+
+#[derive(Accounts)]
+pub struct WithdrawStream<'info> {
+    #[account(mut)]
+    pub recipient: Signer<'info>,
+
+    #[account(
+        seeds = [VAULT_SEED, vault_config.creator.as_ref()],
+        bump = vault_config.bump,
+        constraint = vault_config.version == VAULT_VERSION @ VaultError::AccountNotMigrated,
+        constraint = !vault_config.paused @ VaultError::VaultPaused,
+    )]
+    pub vault_config: Account<'info, VaultConfig>,
+
+    #[account(
+        mut,
+        seeds = [STREAM_SEED, stream.proposal.as_ref()],
+        bump = stream.bump,
+        has_one = recipient @ VaultError::UnauthorizedSigner,
+        constraint = stream.vault == vault_config.key() @ VaultError::UnauthorizedSigner,
+        constraint = stream.mint == mint.key() @ VaultError::InvalidTransferType,
+    )]
+    pub stream: Account<'info, Stream>,
+
+    /// Proposal that created the stream; passed to the policy program
+    #[account(address = stream.proposal @ VaultError::InvalidTransferType)]
+    pub proposal: Account<'info, Proposal>,
+
+    pub mint: Account<'info, Mint>,
+
+    #[account(
+        mut,
+        seeds = [STREAM_RESERVE_SEED, vault_config.key().as_ref(), mint.key().as_ref()],
+        bump = stream_reserve.bump,
+    )]
+    pub stream_reserve: Account<'info, StreamReserve>,
+
+    #[account(
+        mut,
+        associated_token::mint = mint,
+        associated_token::authority = vault_config,
+    )]
+    pub vault_ata: Account<'info, TokenAccount>,
+
+    #[account(
+        init_if_needed,
+        payer = recipient,
+        associated_token::mint = mint,
+        associated_token::authority = recipient,
+    )]
+    pub recipient_ata: Account<'info, TokenAccount>,
+
+    pub token_program: Program<'info, Token>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+    /// CHECK: must match vault_config.policy_program, validated in handler
+    pub policy_program: Option<UncheckedAccount<'info>>,
+}
+
+pub fn handler(ctx: Context<WithdrawStream>) -> Result<()> {
+    let stream = &mut ctx.accounts.stream;
+    let vault_config = &ctx.accounts.vault_config;
+
+    let amount = stream.withdrawable_amount(Clock::get()?.unix_timestamp);
+    require!(amount > 0, VaultError::NothingToWithdraw);
+    let balance = ctx.accounts.vault_ata.amount;
+    require!(balance >= amount, VaultError::InsufficientBalance);
+
+    let recipient_ata_info = ctx.accounts.recipient_ata.to_account_info();
+    run_policy_check(
+        vault_config,
+        &ctx.accounts.proposal,
+        &recipient_ata_info,
+        ctx.accounts.policy_program.as_ref(),
+        PolicyStage::PreExecution,
+        amount,
+        balance,
+    )?;
+
+    // PDA signer seeds for the vault
+    let creator = vault_config.creator;
+    let bump = [vault_config.bump];
+    let signer_seeds: &[&[&[u8]]] = &[&[VAULT_SEED, creator.as_ref(), &bump]];
+
+    transfer(
+        CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            Transfer {
+                from: ctx.accounts.vault_ata.to_account_info(),
+                to: recipient_ata_info.clone(),
+                authority: vault_config.to_account_info(),
+            },
+            signer_seeds,
+        ),
+        amount,
+    )?;
+
+    stream.withdrawn_amount += amount;
+    ctx.accounts.stream_reserve.release(amount);
+
+    run_policy_check(
+        vault_config,
+        &ctx.accounts.proposal,
+        &recipient_ata_info,
+        ctx.accounts.policy_program.as_ref(),
+        PolicyStage::PostExecution,
+        amount,
+        balance - amount,
+    )?;
+
+    Ok(())
+}
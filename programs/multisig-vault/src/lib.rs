@@ -1,3 +1,5 @@
+#[cfg(not(target_os = "solana"))]
+pub mod client;
 pub mod constants;
 pub mod error;
 pub mod instructions;
@@ -27,6 +29,7 @@ pub mod multisig_vault {
         instructions::deposit_token::handler(ctx, amount)
     }
 
+    #[allow(clippy::too_many_arguments)]
     pub fn create_proposal(
         ctx: Context<CreateProposal>,
         recipient: Pubkey,
@@ -34,6 +37,8 @@ pub mod multisig_vault {
         transfer_type: TransferType,
         description: String,
         price_condition: Option<PriceCondition>,
+        memo: Option<OffchainMemo>,
+        allow_partial: bool,
     ) -> Result<()> {
         instructions::create_proposal::handler(
             ctx,
@@ -42,6 +47,26 @@ pub mod multisig_vault {
             transfer_type,
             description,
             price_condition,
+            memo,
+            allow_partial,
+        )
+    }
+
+    pub fn amend_proposal(
+        ctx: Context<AmendProposal>,
+        recipient: Pubkey,
+        amount: u64,
+        description: String,
+        price_condition: Option<PriceCondition>,
+        memo: Option<OffchainMemo>,
+    ) -> Result<()> {
+        instructions::amend_proposal::handler(
+            ctx,
+            recipient,
+            amount,
+            description,
+            price_condition,
+            memo,
         )
     }
 
@@ -49,6 +74,18 @@ pub mod multisig_vault {
         instructions::approve_proposal::handler(ctx)
     }
 
+    pub fn abstain_proposal(ctx: Context<AbstainProposal>) -> Result<()> {
+        instructions::abstain_proposal::handler(ctx)
+    }
+
+    pub fn finalize_proposal(ctx: Context<FinalizeProposal>) -> Result<()> {
+        instructions::finalize_proposal::handler(ctx)
+    }
+
+    pub fn vote_cancel_proposal(ctx: Context<VoteCancelProposal>) -> Result<()> {
+        instructions::vote_cancel_proposal::handler(ctx)
+    }
+
     pub fn reject_proposal(ctx: Context<RejectProposal>) -> Result<()> {
         instructions::reject_proposal::handler(ctx)
     }
@@ -61,7 +98,102 @@ pub mod multisig_vault {
         instructions::execute_token_proposal::handler(ctx)
     }
 
+    pub fn execute_nft_proposal(ctx: Context<ExecuteNftProposal>) -> Result<()> {
+        instructions::execute_nft_proposal::handler(ctx)
+    }
+
+    pub fn execute_core_asset_proposal(ctx: Context<ExecuteCoreAssetProposal>) -> Result<()> {
+        instructions::execute_core_asset_proposal::handler(ctx)
+    }
+
+    pub fn execute_stake_proposal(ctx: Context<ExecuteStakeProposal>) -> Result<()> {
+        instructions::execute_stake_proposal::handler(ctx)
+    }
+
+    pub fn execute_wrap_proposal(ctx: Context<ExecuteWrapProposal>) -> Result<()> {
+        instructions::execute_wrap_proposal::handler(ctx)
+    }
+
     pub fn cancel_proposal(ctx: Context<CancelProposal>) -> Result<()> {
         instructions::cancel_proposal::handler(ctx)
     }
+
+    pub fn execute_stream_proposal(ctx: Context<ExecuteStreamProposal>) -> Result<()> {
+        instructions::execute_stream_proposal::handler(ctx)
+    }
+
+    pub fn withdraw_stream(ctx: Context<WithdrawStream>) -> Result<()> {
+        instructions::withdraw_stream::handler(ctx)
+    }
+
+    pub fn cancel_stream(ctx: Context<CancelStream>) -> Result<()> {
+        instructions::cancel_stream::handler(ctx)
+    }
+
+    pub fn execute_governance_proposal(ctx: Context<ExecuteGovernanceProposal>) -> Result<()> {
+        instructions::execute_governance_proposal::handler(ctx)
+    }
+
+    pub fn pause_vault(ctx: Context<PauseVault>) -> Result<()> {
+        instructions::pause_vault::handler(ctx)
+    }
+
+    pub fn veto_proposal(ctx: Context<VetoProposal>) -> Result<()> {
+        instructions::veto_proposal::handler(ctx)
+    }
+
+    pub fn initiate_recovery(
+        ctx: Context<InitiateRecovery>,
+        new_signers: Vec<Pubkey>,
+        new_threshold: u8,
+    ) -> Result<()> {
+        instructions::initiate_recovery::handler(ctx, new_signers, new_threshold)
+    }
+
+    pub fn approve_recovery(ctx: Context<ApproveRecovery>) -> Result<()> {
+        instructions::approve_recovery::handler(ctx)
+    }
+
+    pub fn cancel_recovery(ctx: Context<CancelRecovery>) -> Result<()> {
+        instructions::cancel_recovery::handler(ctx)
+    }
+
+    pub fn execute_recovery(ctx: Context<ExecuteRecovery>) -> Result<()> {
+        instructions::execute_recovery::handler(ctx)
+    }
+
+    pub fn execute_share_pool_proposal(ctx: Context<ExecuteSharePoolProposal>) -> Result<()> {
+        instructions::execute_share_pool_proposal::handler(ctx)
+    }
+
+    pub fn deposit_for_shares(ctx: Context<DepositForShares>, amount: u64) -> Result<()> {
+        instructions::deposit_for_shares::handler(ctx, amount)
+    }
+
+    pub fn redeem_shares(ctx: Context<RedeemShares>, shares: u64) -> Result<()> {
+        instructions::redeem_shares::handler(ctx, shares)
+    }
+
+    pub fn request_redemption(ctx: Context<RequestRedemption>, shares: u64) -> Result<()> {
+        instructions::request_redemption::handler(ctx, shares)
+    }
+
+    pub fn execute_redemption_proposal(ctx: Context<ExecuteRedemptionProposal>) -> Result<()> {
+        instructions::execute_redemption_proposal::handler(ctx)
+    }
+
+    pub fn get_treasury_value<'info>(
+        ctx: Context<'_, '_, 'info, 'info, GetTreasuryValue<'info>>,
+        max_age_secs: u64,
+    ) -> Result<u64> {
+        instructions::get_treasury_value::handler(ctx, max_age_secs)
+    }
+
+    pub fn migrate_vault(ctx: Context<MigrateVault>) -> Result<()> {
+        instructions::migrate_vault::handler(ctx)
+    }
+
+    pub fn migrate_proposal(ctx: Context<MigrateProposal>) -> Result<()> {
+        instructions::migrate_proposal::handler(ctx)
+    }
 }
@@ -0,0 +1,75 @@
+use crate::constants::*;
+use crate::error::VaultError;
+use anchor_lang::prelude::*;
+
+/// Mints the vault holds, so clients can enumerate holdings without scanning
+/// for token accounts owned by the vault.
+#[account]
+#[derive(InitSpace)]
+pub struct AssetRegistry {
+    pub vault: Pubkey,
+    #[max_len(MAX_TRACKED_ASSETS)]
+    pub assets: Vec<TrackedAsset>,
+    pub bump: u8,
+    /// Pyth SOL/USD feed used to value the vault's lamports.
+    pub sol_price_feed_id: Option<[u8; 32]>,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, InitSpace)]
+pub struct TrackedAsset {
+    pub mint: Pubkey,
+    pub decimals: u8,
+    /// Pyth feed used by `get_treasury_value`.
+    pub price_feed_id: Option<[u8; 32]>,
+}
+
+impl AssetRegistry {
+    /// Adds `mint` unless it is already tracked. Once the registry is full,
+    /// new mints are rejected so the registry never silently misses a holding.
+    pub fn track(&mut self, vault: Pubkey, mint: Pubkey, decimals: u8, bump: u8) -> Result<()> {
+        if self.vault == Pubkey::default() {
+            self.vault = vault;
+            self.bump = bump;
+        }
+
+        if self.assets.iter().any(|a| a.mint == mint) {
+            return Ok(());
+        }
+        require!(
+            self.assets.len() < MAX_TRACKED_ASSETS,
+            VaultError::AssetRegistryFull
+        );
+        self.assets.push(TrackedAsset {
+            mint,
+            decimals,
+            price_feed_id: None,
+        });
+
+        Ok(())
+    }
+
+    pub fn untrack(&mut self, mint: &Pubkey) -> Result<()> {
+        let index = self
+            .assets
+            .iter()
+            .position(|a| a.mint == *mint)
+            .ok_or(error!(VaultError::AssetNotTracked))?;
+        self.assets.remove(index);
+        Ok(())
+    }
+
+    /// Sets the feed for `mint`, or for the vault's SOL when `mint` is `SOL_ASSET`.
+    pub fn set_price_feed(&mut self, mint: &Pubkey, feed_id: Option<[u8; 32]>) -> Result<()> {
+        if *mint == SOL_ASSET {
+            self.sol_price_feed_id = feed_id;
+            return Ok(());
+        }
+        let asset = self
+            .assets
+            .iter_mut()
+            .find(|a| a.mint == *mint)
+            .ok_or(error!(VaultError::AssetNotTracked))?;
+        asset.price_feed_id = feed_id;
+        Ok(())
+    }
+}
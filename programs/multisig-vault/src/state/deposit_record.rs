@@ -0,0 +1,43 @@
+use anchor_lang::prelude::*;
+
+/// Running totals of what one depositor has sent to a vault for one asset.
+#[account]
+#[derive(InitSpace)]
+pub struct DepositRecord {
+    pub vault: Pubkey,
+    pub depositor: Pubkey,
+    /// Token mint, or `SOL_ASSET` for native SOL.
+    pub asset: Pubkey,
+    pub total_deposited: u64,
+    pub deposit_count: u64,
+    pub first_deposit_at: i64,
+    pub last_deposit_at: i64,
+    pub bump: u8,
+}
+
+impl DepositRecord {
+    pub fn record(
+        &mut self,
+        vault: Pubkey,
+        depositor: Pubkey,
+        asset: Pubkey,
+        amount: u64,
+        bump: u8,
+    ) -> Result<()> {
+        let now = Clock::get()?.unix_timestamp;
+
+        if self.deposit_count == 0 {
+            self.vault = vault;
+            self.depositor = depositor;
+            self.asset = asset;
+            self.first_deposit_at = now;
+            self.bump = bump;
+        }
+
+        self.total_deposited = self.total_deposited.saturating_add(amount);
+        self.deposit_count += 1;
+        self.last_deposit_at = now;
+
+        Ok(())
+    }
+}
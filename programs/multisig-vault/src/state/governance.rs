@@ -0,0 +1,96 @@
+use crate::state::{DeadlineRule, RecipientPolicy, RecoveryConfig, ThresholdTier};
+use anchor_lang::prelude::*;
+
+/// Vault configuration changes carried by a `TransferType::Governance`
+/// proposal and applied by `execute_governance_proposal`.
+// New variants go at the end: the Borsh tag is positional and stored proposals
+// depend on it.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq, InitSpace)]
+pub enum GovernanceAction {
+    AddRecipient {
+        recipient: Pubkey,
+    },
+    RemoveRecipient {
+        recipient: Pubkey,
+    },
+    SetRecipientPolicy {
+        policy: RecipientPolicy,
+    },
+    /// Adds a tier, replacing any tier with the same mint and `min_amount`.
+    AddThresholdTier {
+        tier: ThresholdTier,
+    },
+    RemoveThresholdTier {
+        mint: Option<Pubkey>,
+        min_amount: u64,
+    },
+    SetGuardian {
+        guardian: Option<Pubkey>,
+    },
+    Unpause,
+    AddVetoMember {
+        member: Pubkey,
+    },
+    RemoveVetoMember {
+        member: Pubkey,
+    },
+    SetTimelock {
+        timelock_secs: i64,
+    },
+    SetRecovery {
+        recovery: Option<RecoveryConfig>,
+    },
+    SetShareAutoApproval {
+        max_shares: u64,
+    },
+    AddAllowedMint {
+        mint: Pubkey,
+    },
+    RemoveAllowedMint {
+        mint: Pubkey,
+    },
+    AddAllowedDepositor {
+        depositor: Pubkey,
+    },
+    RemoveAllowedDepositor {
+        depositor: Pubkey,
+    },
+    /// Turns enforcement of the deposit allowlists on or off.
+    SetDepositRestrictions {
+        restrict_mints: bool,
+        restrict_depositors: bool,
+    },
+    /// Drops a mint from the vault's `AssetRegistry`.
+    RemoveTrackedAsset {
+        mint: Pubkey,
+    },
+    /// Sets the Pyth feed used to value `mint` (`SOL_ASSET` for SOL).
+    SetAssetPriceFeed {
+        mint: Pubkey,
+        feed_id: Option<[u8; 32]>,
+    },
+    /// Adds a signer, growing the vault account.
+    AddSigner {
+        signer: Pubkey,
+    },
+    /// Removes a signer; fails if the threshold would become unreachable.
+    RemoveSigner {
+        signer: Pubkey,
+    },
+    /// Sets the minimum participation; 0 disables the quorum.
+    SetQuorum {
+        quorum: u8,
+    },
+    /// Sets the voting period (0 for none) and how expired proposals resolve.
+    SetVotingPeriod {
+        voting_period_secs: i64,
+        deadline_rule: DeadlineRule,
+    },
+    SetCancelThreshold {
+        threshold: u8,
+    },
+    /// Registers (or clears) the program CPI'd around every transfer.
+    SetPolicyProgram {
+        program: Option<Pubkey>,
+    },
+}
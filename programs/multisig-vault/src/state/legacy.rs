@@ -0,0 +1,170 @@
+use crate::error::VaultError;
+use crate::state::{PriceCondition, ProposalStatus, TransferType};
+use anchor_lang::prelude::*;
+
+/// Signer cap of the original, unversioned account layouts.
+pub const LEGACY_MAX_SIGNERS: usize = 10;
+pub const LEGACY_MAX_DESCRIPTION_LEN: usize = 200;
+
+/// `VaultConfig` as it was laid out before account versioning.
+#[derive(AnchorDeserialize, InitSpace)]
+pub struct LegacyVaultConfig {
+    pub creator: Pubkey,
+    #[max_len(LEGACY_MAX_SIGNERS)]
+    pub signers: Vec<Pubkey>,
+    pub threshold: u8,
+    pub proposal_count: u64,
+    pub bump: u8,
+}
+
+/// `Proposal` as it was laid out before account versioning.
+#[derive(AnchorDeserialize, InitSpace)]
+pub struct LegacyProposal {
+    pub vault: Pubkey,
+    pub proposal_id: u64,
+    pub proposer: Pubkey,
+    pub recipient: Pubkey,
+    pub amount: u64,
+    pub transfer_type: LegacyTransferType,
+    #[max_len(LEGACY_MAX_DESCRIPTION_LEN)]
+    pub description: String,
+    pub price_condition: Option<PriceCondition>,
+    #[max_len(LEGACY_MAX_SIGNERS)]
+    pub approvals: Vec<Pubkey>,
+    #[max_len(LEGACY_MAX_SIGNERS)]
+    pub rejections: Vec<Pubkey>,
+    pub status: LegacyProposalStatus,
+    pub bump: u8,
+}
+
+#[derive(AnchorDeserialize, InitSpace)]
+pub enum LegacyTransferType {
+    Sol,
+    SplToken { mint: Pubkey },
+}
+
+#[derive(AnchorDeserialize, InitSpace)]
+pub enum LegacyProposalStatus {
+    Active,
+    Approved,
+    Executed,
+    Rejected,
+    Cancelled,
+}
+
+impl From<LegacyTransferType> for TransferType {
+    fn from(legacy: LegacyTransferType) -> Self {
+        match legacy {
+            LegacyTransferType::Sol => TransferType::Sol,
+            LegacyTransferType::SplToken { mint } => TransferType::SplToken { mint },
+        }
+    }
+}
+
+impl From<LegacyProposalStatus> for ProposalStatus {
+    fn from(legacy: LegacyProposalStatus) -> Self {
+        match legacy {
+            LegacyProposalStatus::Active => ProposalStatus::Active,
+            LegacyProposalStatus::Approved => ProposalStatus::Approved,
+            LegacyProposalStatus::Executed => ProposalStatus::Executed,
+            LegacyProposalStatus::Rejected => ProposalStatus::Rejected,
+            LegacyProposalStatus::Cancelled => ProposalStatus::Cancelled,
+        }
+    }
+}
+
+/// `AccountSerialize` for the versioned accounts: the discriminator followed
+/// by the Borsh encoding, as `#[account]` writes it.
+pub fn write_versioned<T: AnchorSerialize, W: std::io::Write>(
+    account: &T,
+    discriminator: &[u8],
+    writer: &mut W,
+) -> Result<()> {
+    if writer.write_all(discriminator).is_err() || account.serialize(writer).is_err() {
+        return Err(ErrorCode::AccountDidNotSerialize.into());
+    }
+    Ok(())
+}
+
+/// `AccountDeserialize` for the versioned accounts. Unlike `#[account]`, it
+/// checks the layout before decoding, so a legacy account (recognised by its
+/// fixed `legacy_len`, which no current layout reaches) or one on another
+/// version fails with `AccountNotMigrated` rather than a generic decode error.
+pub fn read_versioned<T: AnchorDeserialize>(
+    buf: &mut &[u8],
+    discriminator: &[u8],
+    version: u8,
+    legacy_len: usize,
+) -> Result<T> {
+    if buf.len() < discriminator.len() {
+        return Err(ErrorCode::AccountDiscriminatorNotFound.into());
+    }
+    if &buf[..discriminator.len()] != discriminator {
+        return Err(ErrorCode::AccountDiscriminatorMismatch.into());
+    }
+    require!(
+        buf.len() != legacy_len && buf.get(discriminator.len()) == Some(&version),
+        VaultError::AccountNotMigrated
+    );
+    read_unchecked(buf, discriminator)
+}
+
+/// Decodes the account after the discriminator without checking either.
+pub fn read_unchecked<T: AnchorDeserialize>(buf: &mut &[u8], discriminator: &[u8]) -> Result<T> {
+    let mut data: &[u8] = &buf[discriminator.len()..];
+    T::deserialize(&mut data).map_err(|_| ErrorCode::AccountDidNotDeserialize.into())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::constants::VAULT_VERSION;
+    use crate::state::VaultConfig;
+    use anchor_lang::Discriminator;
+
+    const DISC: &[u8] = &[1, 2, 3, 4, 5, 6, 7, 8];
+
+    fn not_migrated() -> Error {
+        VaultError::AccountNotMigrated.into()
+    }
+
+    #[test]
+    fn read_versioned_round_trips_current_accounts() {
+        let mut data = Vec::new();
+        write_versioned(&(7u8, 42u64), DISC, &mut data).unwrap();
+
+        let read: (u8, u64) = read_versioned(&mut data.as_slice(), DISC, 7, 100).unwrap();
+        assert_eq!(read, (7, 42));
+    }
+
+    #[test]
+    fn read_versioned_flags_legacy_and_other_versions() {
+        let mut data = Vec::new();
+        write_versioned(&(7u8, 42u64), DISC, &mut data).unwrap();
+
+        let legacy = read_versioned::<(u8, u64)>(&mut data.as_slice(), DISC, 7, data.len());
+        assert_eq!(legacy.unwrap_err(), not_migrated());
+
+        let other = read_versioned::<(u8, u64)>(&mut data.as_slice(), DISC, 8, 100);
+        assert_eq!(other.unwrap_err(), not_migrated());
+
+        let wrong = read_versioned::<(u8, u64)>(&mut data.as_slice(), &[0; 8], 7, 100);
+        assert_eq!(
+            wrong.unwrap_err(),
+            ErrorCode::AccountDiscriminatorMismatch.into()
+        );
+    }
+
+    #[test]
+    fn legacy_vault_config_is_not_migrated() {
+        let mut data = VaultConfig::DISCRIMINATOR.to_vec();
+        data.resize(8 + LegacyVaultConfig::INIT_SPACE, 0);
+        // A creator key whose first byte happens to equal the version
+        data[8] = VAULT_VERSION;
+
+        match VaultConfig::try_deserialize(&mut data.as_slice()) {
+            Err(err) => assert_eq!(err, not_migrated()),
+            Ok(_) => panic!("legacy vault deserialized"),
+        }
+    }
+}
@@ -1,5 +1,19 @@
+pub mod asset_registry;
+pub mod deposit_record;
+pub mod governance;
+pub mod legacy;
+pub mod policy_hook;
 pub mod proposal;
+pub mod recovery_request;
+pub mod stream;
 pub mod vault_config;
 
+pub use asset_registry::*;
+pub use deposit_record::*;
+pub use governance::*;
+pub use legacy::*;
+pub use policy_hook::*;
 pub use proposal::*;
+pub use recovery_request::*;
+pub use stream::*;
 pub use vault_config::*;
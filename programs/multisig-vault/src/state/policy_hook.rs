@@ -0,0 +1,158 @@
+use crate::error::VaultError;
+use crate::state::{Proposal, TransferType, VaultConfig};
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::instruction::{AccountMeta, Instruction};
+use anchor_lang::solana_program::program::invoke;
+
+/// Anchor discriminator of the policy program's `check` instruction,
+/// `sha256("global:check")[..8]`.
+pub const POLICY_CHECK_DISCRIMINATOR: [u8; 8] = [238, 251, 184, 43, 83, 233, 244, 65];
+
+/// When a policy check runs relative to the transfer.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq)]
+pub enum PolicyStage {
+    PreExecution,
+    PostExecution,
+}
+
+/// Arguments of the policy program's `check` instruction.
+///
+/// Accounts passed along, all read-only: the vault config, the proposal and
+/// the account receiving the assets. Both accounts are written back before the
+/// call, so the post-execution check sees their updated state. Instant share
+/// redemptions have no proposal: `proposal` is the default key and the vault
+/// config fills the proposal slot.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct PolicyCheck {
+    pub stage: PolicyStage,
+    pub vault: Pubkey,
+    pub proposal: Pubkey,
+    pub proposal_id: u64,
+    pub transfer_type: TransferType,
+    pub recipient: Pubkey,
+    /// Amount moved by this execution, in the units of `Proposal::amount`.
+    pub amount: u64,
+    /// Vault balance of the moved asset: token amount for SPL tokens and
+    /// NFTs, available lamports otherwise.
+    pub vault_balance: u64,
+}
+
+/// CPIs the vault's policy program, if one is registered. An error returned
+/// by the hook aborts the whole execution.
+pub fn run_policy_check<'info>(
+    vault: &Account<'info, VaultConfig>,
+    proposal: &Account<'info, Proposal>,
+    destination: &AccountInfo<'info>,
+    policy_program: Option<&UncheckedAccount<'info>>,
+    stage: PolicyStage,
+    amount: u64,
+    vault_balance: u64,
+) -> Result<()> {
+    if vault.policy_program.is_none() {
+        return Ok(());
+    }
+    write_back(proposal)?;
+
+    let check = PolicyCheck {
+        stage,
+        vault: vault.key(),
+        proposal: proposal.key(),
+        proposal_id: proposal.proposal_id,
+        transfer_type: proposal.transfer_type.clone(),
+        recipient: proposal.recipient,
+        amount,
+        vault_balance,
+    };
+    invoke_policy_program(
+        vault,
+        &proposal.to_account_info(),
+        destination,
+        policy_program,
+        check,
+    )
+}
+
+/// Policy check for an instant share redemption, paid to `holder`.
+pub fn run_redemption_policy_check<'info>(
+    vault: &Account<'info, VaultConfig>,
+    holder: &AccountInfo<'info>,
+    policy_program: Option<&UncheckedAccount<'info>>,
+    stage: PolicyStage,
+    shares: u64,
+    vault_balance: u64,
+) -> Result<()> {
+    if vault.policy_program.is_none() {
+        return Ok(());
+    }
+
+    let check = PolicyCheck {
+        stage,
+        vault: vault.key(),
+        proposal: Pubkey::default(),
+        proposal_id: 0,
+        transfer_type: TransferType::RedeemShares,
+        recipient: holder.key(),
+        amount: shares,
+        vault_balance,
+    };
+    invoke_policy_program(
+        vault,
+        &vault.to_account_info(),
+        holder,
+        policy_program,
+        check,
+    )
+}
+
+fn invoke_policy_program<'info>(
+    vault: &Account<'info, VaultConfig>,
+    proposal_info: &AccountInfo<'info>,
+    destination: &AccountInfo<'info>,
+    policy_program: Option<&UncheckedAccount<'info>>,
+    check: PolicyCheck,
+) -> Result<()> {
+    let Some(expected) = vault.policy_program else {
+        return Ok(());
+    };
+    let policy_program = policy_program.ok_or(error!(VaultError::PolicyProgramMismatch))?;
+    require_keys_eq!(
+        policy_program.key(),
+        expected,
+        VaultError::PolicyProgramMismatch
+    );
+    write_back(vault)?;
+
+    let mut data = POLICY_CHECK_DISCRIMINATOR.to_vec();
+    check.serialize(&mut data)?;
+
+    invoke(
+        &Instruction {
+            program_id: expected,
+            accounts: vec![
+                AccountMeta::new_readonly(vault.key(), false),
+                AccountMeta::new_readonly(proposal_info.key(), false),
+                AccountMeta::new_readonly(destination.key(), false),
+            ],
+            data,
+        },
+        &[
+            vault.to_account_info(),
+            proposal_info.clone(),
+            destination.clone(),
+            policy_program.to_account_info(),
+        ],
+    )?;
+
+    Ok(())
+}
+
+/// Serializes `account` into its data so the policy program reads current
+/// state; Anchor would otherwise only write it back when the instruction ends.
+fn write_back<'info, T: AccountSerialize + AccountDeserialize + Owner + Clone>(
+    account: &Account<'info, T>,
+) -> Result<()> {
+    if account.to_account_info().is_writable {
+        account.exit(&crate::ID)?;
+    }
+    Ok(())
+}
@@ -1,24 +1,95 @@
 use crate::constants::*;
+use crate::error::VaultError;
+use crate::state::{
+    read_unchecked, read_versioned, write_versioned, DeadlineRule, GovernanceAction,
+    LegacyProposal, VaultConfig,
+};
 use anchor_lang::prelude::*;
+use anchor_lang::Discriminator;
+use pyth_solana_receiver_sdk::price_update::PriceUpdateV2;
 
-#[account]
-#[derive(InitSpace)]
+// Account traits are implemented by hand instead of with `#[account]` so
+// that legacy accounts fail with `AccountNotMigrated`; see `read_versioned`.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, InitSpace)]
 pub struct Proposal {
+    /// Layout version, `PROPOSAL_VERSION` for current accounts. Kept first so
+    /// it sits at a fixed offset (byte 8) in every layout.
+    pub version: u8,
     pub vault: Pubkey,
     pub proposal_id: u64,
     pub proposer: Pubkey,
     pub recipient: Pubkey,
     pub amount: u64,
+    /// Lets SOL and token transfers pay out in several executions.
+    pub allow_partial: bool,
+    /// Portion of `amount` already transferred.
+    pub amount_paid: u64,
     pub transfer_type: TransferType,
-    #[max_len(MAX_DESCRIPTION_LEN)]
+    /// Inline description; the account is sized to its actual length.
+    #[max_len(0)]
     pub description: String,
+    /// Hash and location of a longer off-chain document.
+    pub memo: Option<OffchainMemo>,
     pub price_condition: Option<PriceCondition>,
-    #[max_len(MAX_SIGNERS)]
-    pub approvals: Vec<Pubkey>,
-    #[max_len(MAX_SIGNERS)]
-    pub rejections: Vec<Pubkey>,
+    /// Bit `i` is set when `VaultConfig.signers[i]` approved.
+    pub approval_bitmap: u64,
+    /// Bit `i` is set when `VaultConfig.signers[i]` rejected.
+    pub rejection_bitmap: u64,
+    /// Bit `i` is set when `VaultConfig.signers[i]` abstained.
+    pub abstain_bitmap: u64,
+    /// Vault `config_epoch` the bitmaps are indexed against.
+    pub config_epoch: u32,
+    /// Bit `i` is set when `VaultConfig.signers[i]` voted to cancel.
+    pub cancel_bitmap: u64,
+    /// Vault `config_epoch` the cancel bitmap is indexed against. Unlike the
+    /// other bitmaps it is reset, not frozen, when the signer set changes.
+    pub cancel_epoch: u32,
     pub status: ProposalStatus,
     pub bump: u8,
+    /// Approvals required, snapshotted from the vault at creation.
+    pub threshold: u8,
+    /// Votes of any kind required, snapshotted from the vault at creation.
+    pub quorum: u8,
+    /// Earliest execution time; the veto window runs until then.
+    pub executable_at: i64,
+    /// Votes are refused after this time and anyone can finalize.
+    pub voting_ends_at: Option<i64>,
+    /// Vault deadline rule, snapshotted at creation.
+    pub deadline_rule: DeadlineRule,
+    /// Number of times the proposer has edited the proposal.
+    pub amendment_count: u16,
+}
+
+impl Discriminator for Proposal {
+    /// `sha256("account:Proposal")[..8]`, as `#[account]` derives it.
+    const DISCRIMINATOR: &'static [u8] = &[26, 94, 189, 187, 116, 136, 53, 33];
+}
+
+impl Owner for Proposal {
+    fn owner() -> Pubkey {
+        crate::ID
+    }
+}
+
+impl AccountSerialize for Proposal {
+    fn try_serialize<W: std::io::Write>(&self, writer: &mut W) -> Result<()> {
+        write_versioned(self, Self::DISCRIMINATOR, writer)
+    }
+}
+
+impl AccountDeserialize for Proposal {
+    fn try_deserialize(buf: &mut &[u8]) -> Result<Self> {
+        read_versioned(
+            buf,
+            Self::DISCRIMINATOR,
+            PROPOSAL_VERSION,
+            8 + LegacyProposal::INIT_SPACE,
+        )
+    }
+
+    fn try_deserialize_unchecked(buf: &mut &[u8]) -> Result<Self> {
+        read_unchecked(buf, Self::DISCRIMINATOR)
+    }
 }
 
 #[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq, InitSpace)]
@@ -28,12 +99,294 @@ pub enum ProposalStatus {
     Executed,
     Rejected,
     Cancelled,
+    Vetoed,
+    /// Part of `amount` is paid; later executions pay the rest.
+    PartiallyExecuted,
 }
 
 #[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq, InitSpace)]
 pub enum TransferType {
     Sol,
-    SplToken { mint: Pubkey },
+    SplToken {
+        mint: Pubkey,
+    },
+    Stream {
+        mint: Pubkey,
+        start_ts: i64,
+        cliff_ts: i64,
+        end_ts: i64,
+    },
+    CancelStream {
+        stream: Pubkey,
+    },
+    Governance {
+        action: GovernanceAction,
+    },
+    /// Token Metadata NFT, including programmable NFTs.
+    Nft {
+        mint: Pubkey,
+    },
+    /// Metaplex Core asset.
+    CoreAsset {
+        asset: Pubkey,
+    },
+    /// Funds a new vault-controlled stake account with `amount` lamports and
+    /// delegates it.
+    CreateStake {
+        vote_account: Pubkey,
+    },
+    DelegateStake {
+        stake_account: Pubkey,
+        vote_account: Pubkey,
+    },
+    DeactivateStake {
+        stake_account: Pubkey,
+    },
+    /// Withdraws `amount` lamports from the stake account back to the vault.
+    WithdrawStake {
+        stake_account: Pubkey,
+    },
+    /// Moves `amount` vault lamports into the vault's wSOL ATA.
+    WrapSol,
+    /// Closes the vault's wSOL ATA back into vault lamports.
+    UnwrapSol,
+    /// Creates the share mint and turns on pooled-fund mode.
+    CreateSharePool {
+        auto_approve_max_shares: u64,
+    },
+    /// Share holder redemption of `amount` shares, created by `request_redemption`.
+    RedeemShares,
+}
+
+/// Off-chain memo or invoice, identified by the blake3 hash of its content.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq, InitSpace)]
+pub struct OffchainMemo {
+    pub hash: [u8; 32],
+    /// Sized to its actual length, like `Proposal.description`.
+    #[max_len(0)]
+    pub uri: String,
+}
+
+impl Proposal {
+    /// Account size for a proposal with the given description and memo.
+    pub fn space(description: &str, memo: Option<&OffchainMemo>) -> usize {
+        8 + Self::INIT_SPACE + description.len() + memo.map_or(0, |m| m.uri.len())
+    }
+
+    pub fn validate_description(description: &str, memo: Option<&OffchainMemo>) -> Result<()> {
+        require!(
+            description.len() <= MAX_DESCRIPTION_LEN,
+            VaultError::DescriptionTooLong
+        );
+        if let Some(memo) = memo {
+            require!(
+                memo.uri.len() <= MAX_MEMO_URI_LEN,
+                VaultError::MemoUriTooLong
+            );
+        }
+        Ok(())
+    }
+
+    /// Whether the signer set changed since the proposal was created. Its
+    /// bitmaps no longer line up with `vault.signers`, so it can't take votes;
+    /// `finalize_proposal` or `cancel_proposal` closes it instead.
+    pub fn is_stale(&self, vault: &VaultConfig) -> bool {
+        self.config_epoch != vault.config_epoch
+    }
+
+    /// Bitmap bit for `signer`'s vote, checking they are a signer under the
+    /// proposal's config epoch and haven't voted yet. Votes on a stale
+    /// proposal fail with `StaleProposal`.
+    pub fn voter_bit(&self, vault: &VaultConfig, signer: &Pubkey) -> Result<u64> {
+        require!(!self.is_stale(vault), VaultError::StaleProposal);
+        let index = vault
+            .signer_index(signer)
+            .ok_or(error!(VaultError::UnauthorizedSigner))?;
+        let bit = 1u64 << index;
+        require!(
+            (self.approval_bitmap | self.rejection_bitmap | self.abstain_bitmap) & bit == 0,
+            VaultError::AlreadyVoted
+        );
+        Ok(bit)
+    }
+
+    /// Records `signer`'s cancel vote and returns the number of cancel votes.
+    pub fn record_cancel_vote(&mut self, vault: &VaultConfig, signer: &Pubkey) -> Result<usize> {
+        if self.cancel_epoch != vault.config_epoch {
+            self.cancel_bitmap = 0;
+            self.cancel_epoch = vault.config_epoch;
+        }
+        let index = vault
+            .signer_index(signer)
+            .ok_or(error!(VaultError::UnauthorizedSigner))?;
+        let bit = 1u64 << index;
+        require!(self.cancel_bitmap & bit == 0, VaultError::AlreadyVoted);
+        self.cancel_bitmap |= bit;
+        Ok(self.cancel_bitmap.count_ones() as usize)
+    }
+
+    pub fn approval_count(&self) -> usize {
+        self.approval_bitmap.count_ones() as usize
+    }
+
+    pub fn rejection_count(&self) -> usize {
+        self.rejection_bitmap.count_ones() as usize
+    }
+
+    pub fn abstain_count(&self) -> usize {
+        self.abstain_bitmap.count_ones() as usize
+    }
+
+    /// Resolves an active proposal after a vote. It is approved once both the
+    /// threshold and the quorum are met, and rejected once the threshold can
+    /// no longer be reached by the signers who haven't voted.
+    pub fn refresh_status(&mut self, vault: &VaultConfig) -> Result<()> {
+        let approvals = self.approval_count();
+        let votes = approvals + self.rejection_count() + self.abstain_count();
+        let remaining = vault.signers.len().saturating_sub(votes);
+
+        if approvals >= self.threshold as usize && votes >= self.quorum as usize {
+            self.mark_approved(vault.timelock_secs)?;
+        } else if approvals + remaining < self.threshold as usize
+            || votes + remaining < self.quorum as usize
+        {
+            self.status = ProposalStatus::Rejected;
+        }
+
+        Ok(())
+    }
+
+    /// Fails once the voting period has ended.
+    pub fn require_voting_open(&self) -> Result<()> {
+        if let Some(voting_ends_at) = self.voting_ends_at {
+            require!(
+                Clock::get()?.unix_timestamp < voting_ends_at,
+                VaultError::VotingClosed
+            );
+        }
+        Ok(())
+    }
+
+    /// Resolves a proposal still active after its voting period, applying
+    /// the deadline rule when the threshold wasn't reached.
+    pub fn resolve_after_deadline(&mut self, vault: &VaultConfig) -> Result<()> {
+        let approvals = self.approval_count();
+        let rejections = self.rejection_count();
+        let quorum_met = approvals + rejections + self.abstain_count() >= self.quorum as usize;
+        let threshold_met = approvals >= self.threshold as usize;
+
+        // Governance changes and proposals that needed more than the base
+        // threshold (tiers, unlisted recipients) always need the threshold.
+        let majority_allowed = !matches!(self.transfer_type, TransferType::Governance { .. })
+            && self.threshold <= vault.threshold;
+
+        let passed = match self.deadline_rule {
+            DeadlineRule::SimpleMajority if majority_allowed => {
+                quorum_met && (threshold_met || approvals > rejections)
+            }
+            _ => threshold_met && quorum_met,
+        };
+
+        if passed {
+            self.mark_approved(vault.timelock_secs)
+        } else {
+            self.status = ProposalStatus::Rejected;
+            Ok(())
+        }
+    }
+
+    /// Moves the proposal to `Approved`, starting the vault's time lock.
+    pub fn mark_approved(&mut self, timelock_secs: i64) -> Result<()> {
+        self.status = ProposalStatus::Approved;
+        self.executable_at = Clock::get()?
+            .unix_timestamp
+            .checked_add(timelock_secs)
+            .ok_or(error!(VaultError::ArithmeticOverflow))?;
+        Ok(())
+    }
+
+    /// Amount to transfer now given `available` funds. Partial proposals pay
+    /// what they can; others need the whole remainder.
+    pub fn next_payment(&self, available: u64) -> Result<u64> {
+        let remaining = self.amount - self.amount_paid;
+        let payment = if self.allow_partial {
+            remaining.min(available)
+        } else {
+            remaining
+        };
+        require!(
+            available >= payment && (payment > 0 || remaining == 0),
+            VaultError::InsufficientBalance
+        );
+        Ok(payment)
+    }
+
+    /// Records a transfer, leaving the proposal `PartiallyExecuted` until
+    /// the full amount has been paid.
+    pub fn record_payment(&mut self, payment: u64) {
+        self.amount_paid += payment;
+        self.status = if self.amount_paid >= self.amount {
+            ProposalStatus::Executed
+        } else {
+            ProposalStatus::PartiallyExecuted
+        };
+    }
+
+    /// Checks that the proposal is approved and its time lock has elapsed.
+    pub fn require_executable(&self) -> Result<()> {
+        require!(
+            self.status != ProposalStatus::Vetoed,
+            VaultError::ProposalVetoed
+        );
+        require!(
+            matches!(
+                self.status,
+                ProposalStatus::Approved | ProposalStatus::PartiallyExecuted
+            ),
+            VaultError::ProposalNotActive
+        );
+        require!(
+            Clock::get()?.unix_timestamp >= self.executable_at,
+            VaultError::TimelockActive
+        );
+        Ok(())
+    }
+}
+
+impl TransferType {
+    /// Whether executing this proposal pays out to `Proposal.recipient`.
+    pub fn pays_recipient(&self) -> bool {
+        matches!(
+            self,
+            TransferType::Sol
+                | TransferType::SplToken { .. }
+                | TransferType::Stream { .. }
+                | TransferType::Nft { .. }
+                | TransferType::CoreAsset { .. }
+        )
+    }
+
+    /// NFT and Core asset proposals always move exactly one asset, so their
+    /// amount can't be picked to dodge amount tiers.
+    pub fn validate_amount(&self, amount: u64) -> Result<()> {
+        if matches!(
+            self,
+            TransferType::Nft { .. } | TransferType::CoreAsset { .. }
+        ) {
+            require!(amount == 1, VaultError::InvalidAmount);
+        }
+        Ok(())
+    }
+
+    /// Token mint moved by this proposal, `None` for SOL and non-transfer proposals.
+    pub fn mint(&self) -> Option<Pubkey> {
+        match self {
+            TransferType::SplToken { mint }
+            | TransferType::Stream { mint, .. }
+            | TransferType::Nft { mint } => Some(*mint),
+            _ => None,
+        }
+    }
 }
 
 #[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq, InitSpace)]
@@ -43,3 +396,23 @@ pub struct PriceCondition {
     pub max_price: Option<i64>, // price scaled to feed exponent
     pub max_age_secs: u64,      // maximum staleness in seconds
 }
+
+impl PriceCondition {
+    /// Pyth price gate shared by the execute handlers.
+    pub fn verify(&self, price_update: Option<&Account<PriceUpdateV2>>) -> Result<()> {
+        let price_update = price_update.ok_or(error!(VaultError::PriceConditionNotMet))?;
+
+        let price = price_update
+            .get_price_no_older_than(&Clock::get()?, self.max_age_secs, &self.feed_id)
+            .map_err(|_| error!(VaultError::StalePriceFeed))?;
+
+        if let Some(min_price) = self.min_price {
+            require!(price.price >= min_price, VaultError::PriceConditionNotMet);
+        }
+        if let Some(max_price) = self.max_price {
+            require!(price.price <= max_price, VaultError::PriceConditionNotMet);
+        }
+
+        Ok(())
+    }
+}
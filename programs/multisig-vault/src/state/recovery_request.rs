@@ -0,0 +1,135 @@
+use crate::constants::*;
+use crate::error::VaultError;
+use crate::state::RecoveryConfig;
+use anchor_lang::prelude::*;
+
+/// Pending signer-set replacement started by the vault's recovery keys.
+#[account]
+#[derive(InitSpace)]
+pub struct RecoveryRequest {
+    pub vault: Pubkey,
+    pub initiator: Pubkey,
+    /// Sized to the proposed set; see `RecoveryRequest::space`.
+    #[max_len(0)]
+    pub new_signers: Vec<Pubkey>,
+    pub new_threshold: u8,
+    #[max_len(MAX_RECOVERY_KEYS)]
+    pub approvals: Vec<Pubkey>,
+    /// Recovery keys that voted to cancel the request.
+    #[max_len(MAX_RECOVERY_KEYS)]
+    pub cancel_votes: Vec<Pubkey>,
+    pub initiated_at: i64,
+    /// Set once approvals reach the recovery threshold.
+    pub executable_at: Option<i64>,
+    /// When the recovery threshold was reached, so a delay raised after
+    /// approval still applies; see `RecoveryRequest::ready_at`.
+    pub approved_at: Option<i64>,
+    pub bump: u8,
+}
+
+impl RecoveryRequest {
+    pub fn space(num_signers: usize) -> usize {
+        8 + Self::INIT_SPACE + num_signers * 32
+    }
+
+    /// Starts the waiting period once the recovery threshold is reached.
+    pub fn refresh(&mut self, recovery_threshold: u8, delay_secs: i64, now: i64) -> Result<()> {
+        if self.executable_at.is_none() && self.approvals.len() >= recovery_threshold as usize {
+            self.executable_at = Some(
+                now.checked_add(delay_secs)
+                    .ok_or(error!(VaultError::ArithmeticOverflow))?,
+            );
+            self.approved_at = Some(now);
+        }
+        Ok(())
+    }
+
+    /// Earliest execution time under the current `delay_secs`: the original
+    /// `executable_at`, pushed back if the delay was raised since approval.
+    pub fn ready_at(&self, delay_secs: i64) -> Result<Option<i64>> {
+        let (Some(executable_at), Some(approved_at)) = (self.executable_at, self.approved_at)
+        else {
+            return Ok(None);
+        };
+        let current = approved_at
+            .checked_add(delay_secs)
+            .ok_or(error!(VaultError::ArithmeticOverflow))?;
+        Ok(Some(executable_at.max(current)))
+    }
+
+    /// Number of `votes` cast by keys still listed in `recovery`, so that
+    /// votes from removed keys stop counting.
+    pub fn count_current(votes: &[Pubkey], recovery: &RecoveryConfig) -> usize {
+        votes.iter().filter(|k| recovery.keys.contains(k)).count()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn request(approvals: Vec<Pubkey>) -> RecoveryRequest {
+        RecoveryRequest {
+            vault: Pubkey::new_unique(),
+            initiator: approvals[0],
+            new_signers: vec![Pubkey::new_unique()],
+            new_threshold: 1,
+            approvals,
+            cancel_votes: Vec::new(),
+            initiated_at: 0,
+            executable_at: None,
+            approved_at: None,
+            bump: 255,
+        }
+    }
+
+    #[test]
+    fn refresh_starts_the_delay_at_the_threshold() {
+        let keys: Vec<Pubkey> = (0..2).map(|_| Pubkey::new_unique()).collect();
+        let mut req = request(vec![keys[0]]);
+
+        req.refresh(2, 100, 1_000).unwrap();
+        assert_eq!(req.executable_at, None);
+
+        req.approvals.push(keys[1]);
+        req.refresh(2, 100, 1_000).unwrap();
+        assert_eq!(req.executable_at, Some(1_100));
+        assert_eq!(req.approved_at, Some(1_000));
+
+        // Later approvals don't restart the delay.
+        req.refresh(2, 100, 5_000).unwrap();
+        assert_eq!(req.executable_at, Some(1_100));
+    }
+
+    #[test]
+    fn refresh_rejects_an_overflowing_delay() {
+        let mut req = request(vec![Pubkey::new_unique()]);
+        assert!(req.refresh(1, i64::MAX, 1).is_err());
+    }
+
+    #[test]
+    fn ready_at_applies_a_raised_delay() {
+        let mut req = request(vec![Pubkey::new_unique()]);
+        assert_eq!(req.ready_at(100).unwrap(), None);
+
+        req.refresh(1, 100, 1_000).unwrap();
+        assert_eq!(req.ready_at(100).unwrap(), Some(1_100));
+        assert_eq!(req.ready_at(500).unwrap(), Some(1_500));
+        // A shorter delay doesn't bring execution forward.
+        assert_eq!(req.ready_at(10).unwrap(), Some(1_100));
+    }
+
+    #[test]
+    fn count_current_ignores_removed_keys() {
+        let keys: Vec<Pubkey> = (0..3).map(|_| Pubkey::new_unique()).collect();
+        let recovery = RecoveryConfig {
+            keys: vec![keys[0], keys[2]],
+            threshold: 2,
+            delay_secs: MIN_RECOVERY_DELAY_SECS,
+        };
+
+        assert_eq!(RecoveryRequest::count_current(&keys, &recovery), 2);
+        assert_eq!(RecoveryRequest::count_current(&keys[1..2], &recovery), 0);
+        assert_eq!(RecoveryRequest::count_current(&[], &recovery), 0);
+    }
+}
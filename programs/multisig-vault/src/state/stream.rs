@@ -0,0 +1,82 @@
+use crate::error::VaultError;
+use anchor_lang::prelude::*;
+
+#[account]
+#[derive(InitSpace)]
+pub struct Stream {
+    pub vault: Pubkey,
+    pub proposal: Pubkey,
+    pub recipient: Pubkey,
+    pub mint: Pubkey,
+    pub total_amount: u64,
+    pub withdrawn_amount: u64,
+    pub start_ts: i64,
+    pub cliff_ts: i64,
+    pub end_ts: i64,
+    pub cancelled_at: Option<i64>,
+    pub bump: u8,
+}
+
+impl Stream {
+    /// Amount vested at `now`: nothing before the cliff, linear from
+    /// `start_ts` to `end_ts`, everything after `end_ts`.
+    pub fn vested_amount(&self, now: i64) -> u64 {
+        if now >= self.end_ts {
+            return self.total_amount;
+        }
+        if now < self.cliff_ts {
+            return 0;
+        }
+
+        let elapsed = (now - self.start_ts) as u128;
+        let duration = (self.end_ts - self.start_ts) as u128;
+        (self.total_amount as u128 * elapsed / duration) as u64
+    }
+
+    /// Vested amount not yet withdrawn.
+    pub fn withdrawable_amount(&self, now: i64) -> u64 {
+        self.vested_amount(now)
+            .saturating_sub(self.withdrawn_amount)
+    }
+}
+
+/// Tokens of one mint in the vault ATA that are promised to streams, per
+/// vault. Token proposals can only spend the ATA balance above `reserved`.
+#[account]
+#[derive(InitSpace)]
+pub struct StreamReserve {
+    pub vault: Pubkey,
+    pub mint: Pubkey,
+    /// Sum of stream amounts not yet withdrawn.
+    pub reserved: u64,
+    pub bump: u8,
+}
+
+impl StreamReserve {
+    /// Reserved amount held by the reserve PDA at `info`, zero if no stream
+    /// for the mint was ever created.
+    pub fn reserved_in(info: &AccountInfo) -> Result<u64> {
+        if info.data_is_empty() {
+            return Ok(0);
+        }
+        require_keys_eq!(
+            *info.owner,
+            crate::ID,
+            ErrorCode::AccountOwnedByWrongProgram
+        );
+        let reserve = StreamReserve::try_deserialize(&mut &info.try_borrow_data()?[..])?;
+        Ok(reserve.reserved)
+    }
+
+    pub fn reserve(&mut self, amount: u64) -> Result<()> {
+        self.reserved = self
+            .reserved
+            .checked_add(amount)
+            .ok_or(error!(VaultError::ArithmeticOverflow))?;
+        Ok(())
+    }
+
+    pub fn release(&mut self, amount: u64) {
+        self.reserved = self.reserved.saturating_sub(amount);
+    }
+}
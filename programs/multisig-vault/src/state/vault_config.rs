@@ -1,13 +1,358 @@
-use crate::constants::MAX_SIGNERS;
+use crate::constants::*;
+use crate::error::VaultError;
+use crate::state::{
+    read_unchecked, read_versioned, write_versioned, LegacyVaultConfig, TransferType,
+};
 use anchor_lang::prelude::*;
+use anchor_lang::Discriminator;
 
-#[account]
-#[derive(InitSpace)]
+// Account traits are implemented by hand instead of with `#[account]` so
+// that legacy accounts fail with `AccountNotMigrated`; see `read_versioned`.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, InitSpace)]
 pub struct VaultConfig {
+    /// Layout version, `VAULT_VERSION` for current accounts. Kept first so it
+    /// sits at a fixed offset (byte 8) in every layout.
+    pub version: u8,
     pub creator: Pubkey,
-    #[max_len(MAX_SIGNERS)]
+    /// Sized to the current membership; see `VaultConfig::space`.
+    #[max_len(0)]
     pub signers: Vec<Pubkey>,
     pub threshold: u8,
+    /// Minimum number of votes, including abstentions, for a proposal to pass.
+    pub quorum: u8,
+    /// Cancel votes needed to cancel an active or approved proposal.
+    pub cancel_threshold: u8,
     pub proposal_count: u64,
     pub bump: u8,
+    /// Bumped whenever `signers` changes, since proposal vote bitmaps are
+    /// indexed by signer position.
+    pub config_epoch: u32,
+    pub recipient_policy: RecipientPolicy,
+    #[max_len(MAX_ADDRESS_BOOK)]
+    pub address_book: Vec<Pubkey>,
+    #[max_len(MAX_THRESHOLD_TIERS)]
+    pub threshold_tiers: Vec<ThresholdTier>,
+    /// Blocks transfers and non-governance proposals while set.
+    pub paused: bool,
+    /// Key allowed to pause the vault without a proposal.
+    pub guardian: Option<Pubkey>,
+    /// Keys that can veto approved proposals during the time lock.
+    #[max_len(MAX_VETO_COUNCIL)]
+    pub veto_council: Vec<Pubkey>,
+    /// Delay between approval and execution.
+    pub timelock_secs: i64,
+    /// How long proposals stay open for votes; 0 means no deadline.
+    pub voting_period_secs: i64,
+    pub deadline_rule: DeadlineRule,
+    pub recovery: Option<RecoveryConfig>,
+    pub share_pool: Option<SharePool>,
+    pub deposit_policy: DepositPolicy,
+    /// Program whose `check` instruction runs before and after each transfer.
+    pub policy_program: Option<Pubkey>,
+}
+
+impl Discriminator for VaultConfig {
+    /// `sha256("account:VaultConfig")[..8]`, as `#[account]` derives it.
+    const DISCRIMINATOR: &'static [u8] = &[99, 86, 43, 216, 184, 102, 119, 77];
+}
+
+impl Owner for VaultConfig {
+    fn owner() -> Pubkey {
+        crate::ID
+    }
+}
+
+impl AccountSerialize for VaultConfig {
+    fn try_serialize<W: std::io::Write>(&self, writer: &mut W) -> Result<()> {
+        write_versioned(self, Self::DISCRIMINATOR, writer)
+    }
+}
+
+impl AccountDeserialize for VaultConfig {
+    fn try_deserialize(buf: &mut &[u8]) -> Result<Self> {
+        read_versioned(
+            buf,
+            Self::DISCRIMINATOR,
+            VAULT_VERSION,
+            8 + LegacyVaultConfig::INIT_SPACE,
+        )
+    }
+
+    fn try_deserialize_unchecked(buf: &mut &[u8]) -> Result<Self> {
+        read_unchecked(buf, Self::DISCRIMINATOR)
+    }
+}
+
+/// How `create_proposal` treats recipients missing from the address book.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, InitSpace)]
+pub enum RecipientPolicy {
+    /// Any recipient is accepted.
+    Open,
+    /// Recipients outside the address book are rejected.
+    AllowlistOnly,
+    /// Recipients outside the address book need `threshold` approvals.
+    ElevatedThreshold { threshold: u8 },
+}
+
+/// How `finalize_proposal` resolves a proposal whose voting period ended
+/// without reaching the threshold.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, InitSpace)]
+pub enum DeadlineRule {
+    /// The proposal is rejected.
+    Reject,
+    /// The proposal passes if quorum was met and approvals outnumber
+    /// rejections. Governance proposals, and proposals whose snapshotted
+    /// threshold is above the vault threshold, still need the threshold.
+    /// Requires a non-zero quorum.
+    SimpleMajority,
+}
+
+/// Transfers of `mint` (`None` for SOL) of at least `min_amount` need
+/// `threshold` approvals.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, InitSpace)]
+pub struct ThresholdTier {
+    pub mint: Option<Pubkey>,
+    pub min_amount: u64,
+    pub threshold: u8,
+}
+
+/// Restrictions on who can deposit and which mints `deposit_token` accepts.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Default, PartialEq, Eq, InitSpace)]
+pub struct DepositPolicy {
+    /// Only `allowed_mints` can be deposited when set.
+    pub restrict_mints: bool,
+    #[max_len(MAX_ALLOWED_MINTS)]
+    pub allowed_mints: Vec<Pubkey>,
+    /// Only `allowed_depositors` can deposit when set.
+    pub restrict_depositors: bool,
+    #[max_len(MAX_ALLOWED_DEPOSITORS)]
+    pub allowed_depositors: Vec<Pubkey>,
+}
+
+impl DepositPolicy {
+    pub fn accepts_mint(&self, mint: &Pubkey) -> bool {
+        !self.restrict_mints || self.allowed_mints.contains(mint)
+    }
+
+    pub fn accepts_depositor(&self, depositor: &Pubkey) -> bool {
+        !self.restrict_depositors || self.allowed_depositors.contains(depositor)
+    }
+}
+
+/// Keys that can replace the signer set, subject to a waiting period.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq, InitSpace)]
+pub struct RecoveryConfig {
+    #[max_len(MAX_RECOVERY_KEYS)]
+    pub keys: Vec<Pubkey>,
+    pub threshold: u8,
+    /// Waiting period after recovery approval during which signers can cancel.
+    pub delay_secs: i64,
+}
+
+impl RecoveryConfig {
+    pub fn validate(&self) -> Result<()> {
+        require!(
+            !self.keys.is_empty()
+                && self.threshold >= 1
+                && self.threshold as usize <= self.keys.len(),
+            VaultError::InvalidThreshold
+        );
+        require!(
+            self.keys.len() <= MAX_RECOVERY_KEYS,
+            VaultError::TooManyRecoveryKeys
+        );
+        require!(
+            self.delay_secs >= MIN_RECOVERY_DELAY_SECS,
+            VaultError::RecoveryDelayTooShort
+        );
+        require!(!has_duplicates(&self.keys), VaultError::DuplicateSigner);
+        Ok(())
+    }
+}
+
+fn has_duplicates(keys: &[Pubkey]) -> bool {
+    let mut sorted = keys.to_vec();
+    sorted.sort();
+    sorted.windows(2).any(|pair| pair[0] == pair[1])
+}
+
+/// Pooled-fund mode: deposits mint shares against the pool's SOL, and
+/// redemptions burn them for a pro-rata slice of it.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, InitSpace)]
+pub struct SharePool {
+    pub mint: Pubkey,
+    /// Shares that can be redeemed without multisig approval per
+    /// `REDEMPTION_WINDOW_SECS` window, across all holders.
+    pub auto_approve_max_shares: u64,
+    pub mint_bump: u8,
+    /// Lamports backing the shares. Tracked separately from the vault balance
+    /// so direct transfers to the vault can't move the share price.
+    pub pool_lamports: u64,
+    pub window_start: i64,
+    pub redeemed_in_window: u64,
+}
+
+impl SharePool {
+    /// Shares minted for `lamports` deposited into a pool holding `pool_lamports`.
+    pub fn shares_for_deposit(lamports: u64, supply: u64, pool_lamports: u64) -> Result<u64> {
+        if supply == 0 {
+            return Ok(lamports);
+        }
+        require!(pool_lamports > 0, VaultError::SharePoolInsolvent);
+        Ok((lamports as u128 * supply as u128 / pool_lamports as u128) as u64)
+    }
+
+    /// Lamports paid out for burning `shares` from a pool holding `pool_lamports`.
+    pub fn lamports_for_shares(shares: u64, supply: u64, pool_lamports: u64) -> u64 {
+        if supply == 0 {
+            return 0;
+        }
+        (shares as u128 * pool_lamports as u128 / supply as u128) as u64
+    }
+
+    /// Counts `shares` against the auto-approval limit, starting a new window
+    /// once the current one has elapsed.
+    pub fn record_auto_redemption(&mut self, shares: u64, now: i64) -> Result<()> {
+        if now >= self.window_start.saturating_add(REDEMPTION_WINDOW_SECS) {
+            self.window_start = now;
+            self.redeemed_in_window = 0;
+        }
+        let redeemed = self
+            .redeemed_in_window
+            .checked_add(shares)
+            .ok_or(error!(VaultError::ArithmeticOverflow))?;
+        require!(
+            redeemed <= self.auto_approve_max_shares,
+            VaultError::RedemptionNeedsApproval
+        );
+        self.redeemed_in_window = redeemed;
+        Ok(())
+    }
+}
+
+impl VaultConfig {
+    /// Lamports the vault PDA can pay out while staying rent exempt.
+    pub fn available_lamports(vault_info: &AccountInfo) -> Result<u64> {
+        let rent_exempt_min = Rent::get()?.minimum_balance(vault_info.data_len());
+        Ok(vault_info.lamports().saturating_sub(rent_exempt_min))
+    }
+
+    /// Lamports proposals can pay out: `available_lamports` less the share
+    /// pool's lamports, which belong to its holders.
+    pub fn spendable_lamports(&self, vault_info: &AccountInfo) -> Result<u64> {
+        let pooled = self
+            .share_pool
+            .as_ref()
+            .map_or(0, |pool| pool.pool_lamports);
+        Ok(Self::available_lamports(vault_info)?.saturating_sub(pooled))
+    }
+
+    /// Account size for a vault with `num_signers` signers.
+    pub fn space(num_signers: usize) -> usize {
+        8 + Self::INIT_SPACE + num_signers * 32
+    }
+
+    /// Approvals required for a proposal, combining the recipient policy and
+    /// amount tiers for kinds that pay a recipient.
+    pub fn threshold_for_transfer(
+        &self,
+        transfer_type: &TransferType,
+        recipient: &Pubkey,
+        amount: u64,
+    ) -> Result<u8> {
+        // Staked lamports leave the vault's liquid balance, so SOL tiers apply.
+        if matches!(transfer_type, TransferType::CreateStake { .. }) {
+            return Ok(self.threshold_for_amount(None, amount));
+        }
+        if !transfer_type.pays_recipient() {
+            return Ok(self.threshold);
+        }
+        let recipient_threshold = self
+            .threshold_for_recipient(recipient)
+            .ok_or(error!(VaultError::RecipientNotAllowed))?;
+        Ok(recipient_threshold.max(self.threshold_for_amount(transfer_type.mint(), amount)))
+    }
+
+    /// Voting deadline for a proposal created at `now`.
+    pub fn voting_deadline(&self, now: i64) -> Result<Option<i64>> {
+        if self.voting_period_secs == 0 {
+            return Ok(None);
+        }
+        now.checked_add(self.voting_period_secs)
+            .map(Some)
+            .ok_or(error!(VaultError::ArithmeticOverflow))
+    }
+
+    /// Starts a new signer-set epoch after `signers` changes.
+    pub fn bump_config_epoch(&mut self) -> Result<()> {
+        self.config_epoch = self
+            .config_epoch
+            .checked_add(1)
+            .ok_or(error!(VaultError::ArithmeticOverflow))?;
+        Ok(())
+    }
+
+    /// Highest approval count any threshold tier or the recipient policy asks for.
+    pub fn max_policy_threshold(&self) -> u8 {
+        let elevated = match self.recipient_policy {
+            RecipientPolicy::ElevatedThreshold { threshold } => threshold,
+            _ => 0,
+        };
+        self.threshold_tiers
+            .iter()
+            .map(|tier| tier.threshold)
+            .fold(elevated, u8::max)
+    }
+
+    /// Caps the quorum, cancel threshold, threshold tiers and elevated
+    /// recipient threshold at the signer count, so a smaller signer set can
+    /// still reach them.
+    pub fn clamp_to_signers(&mut self) {
+        let max = self.signers.len().min(u8::MAX as usize) as u8;
+        self.quorum = self.quorum.min(max);
+        self.cancel_threshold = self.cancel_threshold.min(max);
+        for tier in self.threshold_tiers.iter_mut() {
+            tier.threshold = tier.threshold.min(max);
+        }
+        if let RecipientPolicy::ElevatedThreshold { threshold } = &mut self.recipient_policy {
+            *threshold = (*threshold).min(max);
+        }
+    }
+
+    /// Position of `signer`, which is its bit in proposal vote bitmaps.
+    pub fn signer_index(&self, signer: &Pubkey) -> Option<usize> {
+        self.signers.iter().position(|s| s == signer)
+    }
+
+    pub fn validate_signers(signers: &[Pubkey], threshold: u8) -> Result<()> {
+        require!(
+            !signers.is_empty() && threshold >= 1 && threshold as usize <= signers.len(),
+            VaultError::InvalidThreshold
+        );
+        require!(signers.len() <= MAX_SIGNERS, VaultError::TooManySigners);
+        require!(!has_duplicates(signers), VaultError::DuplicateSigner);
+        Ok(())
+    }
+
+    /// Approvals required for a transfer to `recipient` under the recipient policy.
+    pub fn threshold_for_recipient(&self, recipient: &Pubkey) -> Option<u8> {
+        if self.address_book.contains(recipient) {
+            return Some(self.threshold);
+        }
+
+        match self.recipient_policy {
+            RecipientPolicy::Open => Some(self.threshold),
+            RecipientPolicy::AllowlistOnly => None,
+            RecipientPolicy::ElevatedThreshold { threshold } => Some(threshold.max(self.threshold)),
+        }
+    }
+
+    /// Approvals required to move `amount` of `mint` under the threshold tiers.
+    pub fn threshold_for_amount(&self, mint: Option<Pubkey>, amount: u64) -> u8 {
+        self.threshold_tiers
+            .iter()
+            .filter(|tier| tier.mint == mint && amount >= tier.min_amount)
+            .map(|tier| tier.threshold)
+            .fold(self.threshold, u8::max)
+    }
 }
@@ -0,0 +1,150 @@
+"""Writes the legacy (pre-versioning) VaultConfig and Proposal fixtures that
+the migration tests load through `[[test.validator.account]]` in Anchor.toml.
+
+    python3 tests/fixtures/legacy_accounts.py
+
+Keys are derived from fixed labels so the output is reproducible; the test
+file hard-codes the same addresses.
+"""
+
+import base64
+import hashlib
+import json
+import os
+import struct
+
+PROGRAM_ID = "EL9AsYrsmVDm4HTd7dCWnJJgadksCGkJfyFCw4WTfaZp"
+LEGACY_MAX_SIGNERS = 10
+LEGACY_MAX_DESCRIPTION_LEN = 200
+# 8 + LegacyVaultConfig::INIT_SPACE and 8 + LegacyProposal::INIT_SPACE
+LEGACY_VAULT_LEN = 374
+LEGACY_PROPOSAL_LEN = 1066
+
+B58 = "123456789ABCDEFGHJKLMNPQRSTUVWXYZabcdefghijkmnopqrstuvwxyz"
+
+
+def b58encode(raw: bytes) -> str:
+    n = int.from_bytes(raw, "big")
+    out = ""
+    while n:
+        n, r = divmod(n, 58)
+        out = B58[r] + out
+    return "1" * (len(raw) - len(raw.lstrip(b"\0"))) + out
+
+
+def b58decode(text: str) -> bytes:
+    n = 0
+    for c in text:
+        n = n * 58 + B58.index(c)
+    raw = n.to_bytes((n.bit_length() + 7) // 8, "big")
+    return b"\0" * (len(text) - len(text.lstrip("1"))) + raw
+
+
+def key(label: str) -> bytes:
+    return hashlib.sha256(label.encode()).digest()
+
+
+def on_curve(point: bytes) -> bool:
+    """Whether `point` decompresses to an ed25519 point."""
+    p = 2**255 - 19
+    d = -121665 * pow(121666, p - 2, p) % p
+    y = int.from_bytes(point, "little") & ((1 << 255) - 1)
+    y2 = y * y % p
+    x2 = (y2 - 1) * pow(d * y2 + 1, p - 2, p) % p
+    return x2 == 0 or pow(x2, (p - 1) // 2, p) == 1
+
+
+def find_program_address(seeds: list) -> tuple:
+    program = b58decode(PROGRAM_ID)
+    for bump in range(255, -1, -1):
+        digest = hashlib.sha256(
+            b"".join(seeds) + bytes([bump]) + program + b"ProgramDerivedAddress"
+        ).digest()
+        if not on_curve(digest):
+            return digest, bump
+    raise ValueError("no bump found")
+
+
+def discriminator(name: str) -> bytes:
+    return hashlib.sha256(f"account:{name}".encode()).digest()[:8]
+
+
+def pubkeys(keys: list) -> bytes:
+    return struct.pack("<I", len(keys)) + b"".join(keys)
+
+
+def account(address: bytes, data: bytes, size: int) -> dict:
+    data = data.ljust(size, b"\0")
+    return {
+        "pubkey": b58encode(address),
+        "account": {
+            "lamports": (128 + size) * 3480 * 2,
+            "data": [base64.b64encode(data).decode(), "base64"],
+            "owner": PROGRAM_ID,
+            "executable": False,
+            "rentEpoch": 18446744073709551615,
+            "space": size,
+        },
+    }
+
+
+def main():
+    creator = key("legacy-creator")
+    signers = [key(f"legacy-signer-{i}") for i in range(3)]
+    outsider = key("legacy-outsider")
+    recipient = key("legacy-recipient")
+
+    vault, vault_bump = find_program_address([b"vault", creator])
+    vault_data = (
+        discriminator("VaultConfig")
+        + creator
+        + pubkeys(signers)
+        + bytes([2])  # threshold
+        + struct.pack("<Q", 1)  # proposal_count
+        + bytes([vault_bump])
+    )
+
+    proposal_id = struct.pack("<Q", 0)
+    proposal, proposal_bump = find_program_address([b"proposal", vault, proposal_id])
+    description = b"Legacy payout"
+    proposal_data = (
+        discriminator("Proposal")
+        + vault
+        + proposal_id
+        + signers[0]  # proposer
+        + recipient
+        + struct.pack("<Q", 1_000_000_000)
+        + bytes([0])  # LegacyTransferType::Sol
+        + struct.pack("<I", len(description))
+        + description
+        + bytes([0])  # no price condition
+        + pubkeys([signers[0], signers[2], outsider])  # approvals
+        + pubkeys([signers[1]])  # rejections
+        + bytes([0])  # LegacyProposalStatus::Active
+        + bytes([proposal_bump])
+    )
+
+    fixtures = {
+        "legacy-vault.json": account(vault, vault_data, LEGACY_VAULT_LEN),
+        "legacy-proposal.json": account(proposal, proposal_data, LEGACY_PROPOSAL_LEN),
+        # Same bytes at addresses that don't match their seeds
+        "legacy-vault-copy.json": account(
+            key("legacy-vault-copy"), vault_data, LEGACY_VAULT_LEN
+        ),
+        "legacy-proposal-copy.json": account(
+            key("legacy-proposal-copy"), proposal_data, LEGACY_PROPOSAL_LEN
+        ),
+    }
+    here = os.path.dirname(os.path.abspath(__file__))
+    for name, fixture in fixtures.items():
+        with open(os.path.join(here, name), "w") as f:
+            json.dump(fixture, f, indent=2)
+            f.write("\n")
+        print(name, fixture["pubkey"])
+    for i, signer in enumerate(signers):
+        print(f"signer {i}", b58encode(signer))
+    print("creator", b58encode(creator))
+
+
+if __name__ == "__main__":
+    main()
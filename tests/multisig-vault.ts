@@ -1,20 +1,30 @@
 import * as anchor from "@coral-xyz/anchor";
 import { Program, BN } from "@coral-xyz/anchor";
 import { MultisigVault } from "../target/types/multisig_vault";
+import { MockPolicy } from "../target/types/mock_policy";
 import {
     PublicKey,
     Keypair,
     SystemProgram,
+    StakeProgram,
+    SYSVAR_CLOCK_PUBKEY,
+    SYSVAR_RENT_PUBKEY,
+    SYSVAR_STAKE_HISTORY_PUBKEY,
     LAMPORTS_PER_SOL,
+    Transaction,
+    TransactionInstruction,
+    SYSVAR_INSTRUCTIONS_PUBKEY,
 } from "@solana/web3.js";
 import {
     createMint,
     createAssociatedTokenAccount,
+    createAccount,
     mintTo,
     getAssociatedTokenAddressSync,
     getAccount,
     TOKEN_PROGRAM_ID,
     ASSOCIATED_TOKEN_PROGRAM_ID,
+    NATIVE_MINT,
 } from "@solana/spl-token";
 import { expect } from "chai";
 
@@ -52,6 +62,48 @@ describe("multisig-vault", () => {
         );
     }
 
+    function getDepositRecordPda(
+        vault: PublicKey,
+        depositor: PublicKey,
+        asset: PublicKey,
+    ): [PublicKey, number] {
+        return PublicKey.findProgramAddressSync(
+            [
+                Buffer.from("deposit"),
+                vault.toBuffer(),
+                depositor.toBuffer(),
+                asset.toBuffer(),
+            ],
+            program.programId,
+        );
+    }
+
+    function getAssetRegistryPda(vault: PublicKey): [PublicKey, number] {
+        return PublicKey.findProgramAddressSync(
+            [Buffer.from("assets"), vault.toBuffer()],
+            program.programId,
+        );
+    }
+
+    function getStreamReservePda(
+        vault: PublicKey,
+        mint: PublicKey,
+    ): [PublicKey, number] {
+        return PublicKey.findProgramAddressSync(
+            [
+                Buffer.from("stream_reserve"),
+                vault.toBuffer(),
+                mint.toBuffer(),
+            ],
+            program.programId,
+        );
+    }
+
+    // Signers whose bit is set in a proposal vote bitmap
+    function decodeVotes(bitmap: BN, signers: PublicKey[]): PublicKey[] {
+        return signers.filter((_, i) => bitmap.testn(i));
+    }
+
     async function airdrop(to: PublicKey, sol: number) {
         const sig = await connection.requestAirdrop(
             to,
@@ -65,6 +117,52 @@ describe("multisig-vault", () => {
         return v.proposalCount.toNumber();
     }
 
+    async function executeGovernance(
+        action: object,
+        assetRegistry: PublicKey | null = null,
+    ): Promise<PublicKey> {
+        const id = await fetchProposalCount();
+        const [proposalPda] = getProposalPda(vaultPda, id);
+
+        await program.methods
+            .createProposal(
+                vaultPda,
+                new BN(0),
+                { governance: { action } },
+                "Governance",
+                null,
+                null,
+                false,
+            )
+            .accounts({
+                proposer: signer1.publicKey,
+                vaultConfig: vaultPda,
+                proposal: proposalPda,
+                systemProgram: SystemProgram.programId,
+            })
+            .rpc();
+        await program.methods
+            .approveProposal()
+            .accounts({
+                signer: signer2.publicKey,
+                vaultConfig: vaultPda,
+                proposal: proposalPda,
+            })
+            .signers([signer2])
+            .rpc();
+        await program.methods
+            .executeGovernanceProposal()
+            .accounts({
+                executor: signer1.publicKey,
+                vaultConfig: vaultPda,
+                proposal: proposalPda,
+                assetRegistry,
+            })
+            .rpc();
+
+        return proposalPda;
+    }
+
     const signersList = [
         signer1.publicKey,
         signer2.publicKey,
@@ -119,6 +217,11 @@ describe("multisig-vault", () => {
                 .accounts({
                     depositor: signer1.publicKey,
                     vaultConfig: vaultPda,
+                    depositRecord: getDepositRecordPda(
+                        vaultPda,
+                        signer1.publicKey,
+                        PublicKey.default,
+                    )[0],
                     systemProgram: SystemProgram.programId,
                 })
                 .rpc();
@@ -126,6 +229,20 @@ describe("multisig-vault", () => {
 
             const after = await connection.getBalance(vaultPda, "confirmed");
             expect(after - before).to.equal(2 * LAMPORTS_PER_SOL);
+
+            const [recordPda] = getDepositRecordPda(
+                vaultPda,
+                signer1.publicKey,
+                PublicKey.default,
+            );
+            const record = await program.account.depositRecord.fetch(
+                recordPda,
+            );
+            expect(record.depositor.equals(signer1.publicKey)).to.be.true;
+            expect(record.totalDeposited.toNumber()).to.equal(
+                2 * LAMPORTS_PER_SOL,
+            );
+            expect(record.depositCount.toNumber()).to.equal(1);
         });
     });
 
@@ -176,6 +293,12 @@ describe("multisig-vault", () => {
                     mint: testMint,
                     depositorAta: depositorAta,
                     vaultAta: vaultAta,
+                    depositRecord: getDepositRecordPda(
+                        vaultPda,
+                        signer1.publicKey,
+                        testMint,
+                    )[0],
+                    assetRegistry: getAssetRegistryPda(vaultPda)[0],
                     tokenProgram: TOKEN_PROGRAM_ID,
                     associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
                     systemProgram: SystemProgram.programId,
@@ -184,6 +307,58 @@ describe("multisig-vault", () => {
 
             const vaultTokenAcct = await getAccount(connection, vaultAta);
             expect(Number(vaultTokenAcct.amount)).to.equal(depositAmt);
+
+            const [recordPda] = getDepositRecordPda(
+                vaultPda,
+                signer1.publicKey,
+                testMint,
+            );
+            const record = await program.account.depositRecord.fetch(
+                recordPda,
+            );
+            expect(record.asset.equals(testMint)).to.be.true;
+            expect(record.totalDeposited.toNumber()).to.equal(depositAmt);
+
+            const registry = await program.account.assetRegistry.fetch(
+                getAssetRegistryPda(vaultPda)[0],
+            );
+            expect(registry.assets).to.have.lengthOf(1);
+            expect(registry.assets[0].mint.equals(testMint)).to.be.true;
+            expect(registry.assets[0].decimals).to.equal(TOKEN_DECIMALS);
+        });
+
+        it("rejects a zero-amount deposit", async () => {
+            try {
+                await program.methods
+                    .depositToken(new BN(0))
+                    .accounts({
+                        depositor: signer1.publicKey,
+                        vaultConfig: vaultPda,
+                        mint: testMint,
+                        depositorAta: getAssociatedTokenAddressSync(
+                            testMint,
+                            signer1.publicKey,
+                        ),
+                        vaultAta: getAssociatedTokenAddressSync(
+                            testMint,
+                            vaultPda,
+                            true,
+                        ),
+                        depositRecord: getDepositRecordPda(
+                            vaultPda,
+                            signer1.publicKey,
+                            testMint,
+                        )[0],
+                        assetRegistry: getAssetRegistryPda(vaultPda)[0],
+                        tokenProgram: TOKEN_PROGRAM_ID,
+                        associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+                        systemProgram: SystemProgram.programId,
+                    })
+                    .rpc();
+                expect.fail("should have rejected the amount");
+            } catch (err) {
+                expect(err.error.errorCode.code).to.equal("InvalidAmount");
+            }
         });
     });
 
@@ -200,6 +375,8 @@ describe("multisig-vault", () => {
                     { sol: {} },
                     "Pay contributor",
                     null,
+                    null,
+                    false,
                 )
                 .accounts({
                     proposer: signer1.publicKey,
@@ -214,8 +391,9 @@ describe("multisig-vault", () => {
             expect(prop.recipient.equals(recipient.publicKey)).to.be.true;
             expect(prop.amount.toNumber()).to.equal(LAMPORTS_PER_SOL);
             expect(prop.status).to.deep.equal({ active: {} });
-            expect(prop.approvals).to.have.lengthOf(1);
-            expect(prop.approvals[0].equals(signer1.publicKey)).to.be.true;
+            const approvers = decodeVotes(prop.approvalBitmap, signersList);
+            expect(approvers).to.have.lengthOf(1);
+            expect(approvers[0].equals(signer1.publicKey)).to.be.true;
         });
 
         it("creates an SPL token withdrawal proposal", async () => {
@@ -229,6 +407,8 @@ describe("multisig-vault", () => {
                     { splToken: { mint: testMint } },
                     "Token grant",
                     null,
+                    null,
+                    false,
                 )
                 .accounts({
                     proposer: signer1.publicKey,
@@ -260,7 +440,9 @@ describe("multisig-vault", () => {
                 .rpc();
 
             const prop = await program.account.proposal.fetch(solProposalPda);
-            expect(prop.approvals).to.have.lengthOf(2);
+            expect(
+                decodeVotes(prop.approvalBitmap, signersList),
+            ).to.have.lengthOf(2);
             expect(prop.status).to.deep.equal({ approved: {} });
         });
 
@@ -295,6 +477,8 @@ describe("multisig-vault", () => {
                     { sol: {} },
                     "Proposal to reject",
                     null,
+                    null,
+                    false,
                 )
                 .accounts({
                     proposer: signer1.publicKey,
@@ -318,7 +502,9 @@ describe("multisig-vault", () => {
 
             const prop =
                 await program.account.proposal.fetch(rejectProposalPda);
-            expect(prop.rejections).to.have.lengthOf(1);
+            expect(
+                decodeVotes(prop.rejectionBitmap, signersList),
+            ).to.have.lengthOf(1);
             expect(prop.status).to.deep.equal({ active: {} });
         });
 
@@ -335,7 +521,9 @@ describe("multisig-vault", () => {
 
             const prop =
                 await program.account.proposal.fetch(rejectProposalPda);
-            expect(prop.rejections).to.have.lengthOf(2);
+            expect(
+                decodeVotes(prop.rejectionBitmap, signersList),
+            ).to.have.lengthOf(2);
             expect(prop.status).to.deep.equal({ rejected: {} });
         });
     });
@@ -357,6 +545,7 @@ describe("multisig-vault", () => {
                     recipient: recipient.publicKey,
                     priceUpdate: null,
                     systemProgram: SystemProgram.programId,
+                    policyProgram: null,
                 })
                 .rpc();
             await connection.confirmTransaction(sig, "confirmed");
@@ -395,12 +584,15 @@ describe("multisig-vault", () => {
                     proposal: splProposalPda,
                     recipient: recipient.publicKey,
                     mint: testMint,
-                    vaultAta: vaultAta,
+                    vaultTokenAccount: vaultAta,
+                    streamReserve: getStreamReservePda(vaultPda, testMint)[0],
                     recipientAta: recipientAta,
+                    recipientTokenAccount: null,
                     tokenProgram: TOKEN_PROGRAM_ID,
                     associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
                     systemProgram: SystemProgram.programId,
                     priceUpdate: null,
+                    policyProgram: null,
                 })
                 .rpc();
 
@@ -431,6 +623,8 @@ describe("multisig-vault", () => {
                     { sol: {} },
                     "Will be cancelled",
                     null,
+                    null,
+                    false,
                 )
                 .accounts({
                     proposer: signer1.publicKey,
@@ -456,4 +650,3110 @@ describe("multisig-vault", () => {
             expect(prop.status).to.deep.equal({ cancelled: {} });
         });
     });
+
+    // ------- streams -------
+    describe("streams", () => {
+        let streamProposalPda: PublicKey;
+        let streamPda: PublicKey;
+        const streamTotal = 10_000 * 10 ** TOKEN_DECIMALS;
+
+        function getStreamPda(proposal: PublicKey): [PublicKey, number] {
+            return PublicKey.findProgramAddressSync(
+                [Buffer.from("stream"), proposal.toBuffer()],
+                program.programId,
+            );
+        }
+
+        before(async () => {
+            const now = Math.floor(Date.now() / 1000);
+            const id = await fetchProposalCount();
+            [streamProposalPda] = getProposalPda(vaultPda, id);
+            [streamPda] = getStreamPda(streamProposalPda);
+
+            await program.methods
+                .createProposal(
+                    recipient.publicKey,
+                    new BN(streamTotal),
+                    {
+                        stream: {
+                            mint: testMint,
+                            startTs: new BN(now - 60),
+                            cliffTs: new BN(now - 60),
+                            endTs: new BN(now + 3600),
+                        },
+                    },
+                    "Vesting grant",
+                    null,
+                    null,
+                    false,
+                )
+                .accounts({
+                    proposer: signer1.publicKey,
+                    vaultConfig: vaultPda,
+                    proposal: streamProposalPda,
+                    systemProgram: SystemProgram.programId,
+                })
+                .rpc();
+
+            await program.methods
+                .approveProposal()
+                .accounts({
+                    signer: signer2.publicKey,
+                    vaultConfig: vaultPda,
+                    proposal: streamProposalPda,
+                })
+                .signers([signer2])
+                .rpc();
+        });
+
+        it("executes an approved stream proposal", async () => {
+            await program.methods
+                .executeStreamProposal()
+                .accounts({
+                    executor: signer1.publicKey,
+                    vaultConfig: vaultPda,
+                    proposal: streamProposalPda,
+                    stream: streamPda,
+                    mint: testMint,
+                    vaultAta: getAssociatedTokenAddressSync(
+                        testMint,
+                        vaultPda,
+                        true,
+                    ),
+                    streamReserve: getStreamReservePda(vaultPda, testMint)[0],
+                    priceUpdate: null,
+                    systemProgram: SystemProgram.programId,
+                    policyProgram: null,
+                })
+                .rpc();
+
+            const stream = await program.account.stream.fetch(streamPda);
+            expect(stream.recipient.equals(recipient.publicKey)).to.be.true;
+            expect(stream.totalAmount.toNumber()).to.equal(streamTotal);
+            expect(stream.withdrawnAmount.toNumber()).to.equal(0);
+
+            const reserve = await program.account.streamReserve.fetch(
+                getStreamReservePda(vaultPda, testMint)[0],
+            );
+            expect(reserve.reserved.toNumber()).to.equal(streamTotal);
+
+            const prop =
+                await program.account.proposal.fetch(streamProposalPda);
+            expect(prop.status).to.deep.equal({ executed: {} });
+        });
+
+        it("recipient withdraws the vested portion", async () => {
+            await program.methods
+                .withdrawStream()
+                .accounts({
+                    recipient: recipient.publicKey,
+                    vaultConfig: vaultPda,
+                    stream: streamPda,
+                    proposal: streamProposalPda,
+                    mint: testMint,
+                    streamReserve: getStreamReservePda(vaultPda, testMint)[0],
+                    vaultAta: getAssociatedTokenAddressSync(
+                        testMint,
+                        vaultPda,
+                        true,
+                    ),
+                    recipientAta: getAssociatedTokenAddressSync(
+                        testMint,
+                        recipient.publicKey,
+                    ),
+                    tokenProgram: TOKEN_PROGRAM_ID,
+                    associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+                    systemProgram: SystemProgram.programId,
+                    policyProgram: null,
+                })
+                .signers([recipient])
+                .rpc();
+
+            const stream = await program.account.stream.fetch(streamPda);
+            expect(stream.withdrawnAmount.toNumber()).to.be.greaterThan(0);
+            expect(stream.withdrawnAmount.toNumber()).to.be.lessThan(
+                streamTotal,
+            );
+
+            const reserve = await program.account.streamReserve.fetch(
+                getStreamReservePda(vaultPda, testMint)[0],
+            );
+            expect(reserve.reserved.toNumber()).to.equal(
+                streamTotal - stream.withdrawnAmount.toNumber(),
+            );
+        });
+
+        it("keeps reserved stream funds out of token proposals", async () => {
+            const vaultAta = getAssociatedTokenAddressSync(
+                testMint,
+                vaultPda,
+                true,
+            );
+            const reservePda = getStreamReservePda(vaultPda, testMint)[0];
+            const balance = Number(
+                (await getAccount(connection, vaultAta)).amount,
+            );
+            const reserved = (
+                await program.account.streamReserve.fetch(reservePda)
+            ).reserved.toNumber();
+
+            const id = await fetchProposalCount();
+            const [proposalPda] = getProposalPda(vaultPda, id);
+            await program.methods
+                .createProposal(
+                    recipient.publicKey,
+                    new BN(balance - reserved + 1),
+                    { splToken: { mint: testMint } },
+                    "Dips into stream funds",
+                    null,
+                    null,
+                    false,
+                )
+                .accounts({
+                    proposer: signer1.publicKey,
+                    vaultConfig: vaultPda,
+                    proposal: proposalPda,
+                    systemProgram: SystemProgram.programId,
+                })
+                .rpc();
+            await program.methods
+                .approveProposal()
+                .accounts({
+                    signer: signer2.publicKey,
+                    vaultConfig: vaultPda,
+                    proposal: proposalPda,
+                })
+                .signers([signer2])
+                .rpc();
+
+            try {
+                await program.methods
+                    .executeTokenProposal()
+                    .accounts({
+                        executor: signer1.publicKey,
+                        vaultConfig: vaultPda,
+                        proposal: proposalPda,
+                        recipient: recipient.publicKey,
+                        mint: testMint,
+                        vaultTokenAccount: vaultAta,
+                        streamReserve: reservePda,
+                        recipientAta: getAssociatedTokenAddressSync(
+                            testMint,
+                            recipient.publicKey,
+                        ),
+                        recipientTokenAccount: null,
+                        tokenProgram: TOKEN_PROGRAM_ID,
+                        associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+                        systemProgram: SystemProgram.programId,
+                        priceUpdate: null,
+                        policyProgram: null,
+                    })
+                    .rpc();
+                expect.fail("should not spend reserved stream funds");
+            } catch (err) {
+                expect(err.error.errorCode.code).to.equal(
+                    "InsufficientBalance",
+                );
+            }
+        });
+
+        it("cancels the stream via an approved proposal", async () => {
+            const id = await fetchProposalCount();
+            const [cancelStreamProposalPda] = getProposalPda(vaultPda, id);
+
+            await program.methods
+                .createProposal(
+                    recipient.publicKey,
+                    new BN(0),
+                    { cancelStream: { stream: streamPda } },
+                    "Stop vesting",
+                    null,
+                    null,
+                    false,
+                )
+                .accounts({
+                    proposer: signer1.publicKey,
+                    vaultConfig: vaultPda,
+                    proposal: cancelStreamProposalPda,
+                    systemProgram: SystemProgram.programId,
+                })
+                .rpc();
+            await program.methods
+                .approveProposal()
+                .accounts({
+                    signer: signer2.publicKey,
+                    vaultConfig: vaultPda,
+                    proposal: cancelStreamProposalPda,
+                })
+                .signers([signer2])
+                .rpc();
+
+            await program.methods
+                .cancelStream()
+                .accounts({
+                    executor: signer1.publicKey,
+                    vaultConfig: vaultPda,
+                    proposal: cancelStreamProposalPda,
+                    stream: streamPda,
+                    streamReserve: getStreamReservePda(vaultPda, testMint)[0],
+                    vaultAta: getAssociatedTokenAddressSync(
+                        testMint,
+                        vaultPda,
+                        true,
+                    ),
+                    policyProgram: null,
+                })
+                .rpc();
+
+            const stream = await program.account.stream.fetch(streamPda);
+            expect(stream.cancelledAt).to.not.be.null;
+            expect(stream.totalAmount.toNumber()).to.be.lessThan(streamTotal);
+
+            const reserve = await program.account.streamReserve.fetch(
+                getStreamReservePda(vaultPda, testMint)[0],
+            );
+            expect(reserve.reserved.toNumber()).to.equal(
+                stream.totalAmount.sub(stream.withdrawnAmount).toNumber(),
+            );
+        });
+    });
+
+    // ------- address book -------
+    describe("address book", () => {
+        it("adds a recipient through governance", async () => {
+            await executeGovernance({
+                addRecipient: { recipient: recipient.publicKey },
+            });
+
+            const vault = await program.account.vaultConfig.fetch(vaultPda);
+            expect(vault.addressBook).to.have.lengthOf(1);
+            expect(vault.addressBook[0].equals(recipient.publicKey)).to.be
+                .true;
+        });
+
+        it("rejects unlisted recipients under an allowlist policy", async () => {
+            await executeGovernance({
+                setRecipientPolicy: { policy: { allowlistOnly: {} } },
+            });
+
+            const id = await fetchProposalCount();
+            const [proposalPda] = getProposalPda(vaultPda, id);
+
+            try {
+                await program.methods
+                    .createProposal(
+                        nonSigner.publicKey,
+                        new BN(LAMPORTS_PER_SOL / 10),
+                        { sol: {} },
+                        "Unlisted recipient",
+                        null,
+                        null,
+                        false,
+                    )
+                    .accounts({
+                        proposer: signer1.publicKey,
+                        vaultConfig: vaultPda,
+                        proposal: proposalPda,
+                        systemProgram: SystemProgram.programId,
+                    })
+                    .rpc();
+                expect.fail("should have rejected unlisted recipient");
+            } catch (err) {
+                expect(err.error.errorCode.code).to.equal(
+                    "RecipientNotAllowed",
+                );
+            }
+        });
+    });
+
+    // ------- threshold tiers -------
+    describe("threshold tiers", () => {
+        it("snapshots a higher threshold for large transfers", async () => {
+            await executeGovernance({
+                addThresholdTier: {
+                    tier: {
+                        mint: null,
+                        minAmount: new BN(10 * LAMPORTS_PER_SOL),
+                        threshold: 3,
+                    },
+                },
+            });
+
+            const smallId = await fetchProposalCount();
+            const [smallPda] = getProposalPda(vaultPda, smallId);
+            await program.methods
+                .createProposal(
+                    recipient.publicKey,
+                    new BN(LAMPORTS_PER_SOL / 10),
+                    { sol: {} },
+                    "Small payment",
+                    null,
+                    null,
+                    false,
+                )
+                .accounts({
+                    proposer: signer1.publicKey,
+                    vaultConfig: vaultPda,
+                    proposal: smallPda,
+                    systemProgram: SystemProgram.programId,
+                })
+                .rpc();
+
+            const largeId = await fetchProposalCount();
+            const [largePda] = getProposalPda(vaultPda, largeId);
+            await program.methods
+                .createProposal(
+                    recipient.publicKey,
+                    new BN(20 * LAMPORTS_PER_SOL),
+                    { sol: {} },
+                    "Large payment",
+                    null,
+                    null,
+                    false,
+                )
+                .accounts({
+                    proposer: signer1.publicKey,
+                    vaultConfig: vaultPda,
+                    proposal: largePda,
+                    systemProgram: SystemProgram.programId,
+                })
+                .rpc();
+
+            const small = await program.account.proposal.fetch(smallPda);
+            const large = await program.account.proposal.fetch(largePda);
+            expect(small.threshold).to.equal(2);
+            expect(large.threshold).to.equal(3);
+
+            await program.methods
+                .approveProposal()
+                .accounts({
+                    signer: signer2.publicKey,
+                    vaultConfig: vaultPda,
+                    proposal: largePda,
+                })
+                .signers([signer2])
+                .rpc();
+
+            const stillActive = await program.account.proposal.fetch(largePda);
+            expect(stillActive.status).to.deep.equal({ active: {} });
+        });
+
+        it("applies SOL tiers to stake proposals", async () => {
+            const id = await fetchProposalCount();
+            const [proposalPda] = getProposalPda(vaultPda, id);
+            await program.methods
+                .createProposal(
+                    vaultPda,
+                    new BN(20 * LAMPORTS_PER_SOL),
+                    {
+                        createStake: {
+                            voteAccount: Keypair.generate().publicKey,
+                        },
+                    },
+                    "Large stake",
+                    null,
+                    null,
+                    false,
+                )
+                .accounts({
+                    proposer: signer1.publicKey,
+                    vaultConfig: vaultPda,
+                    proposal: proposalPda,
+                    systemProgram: SystemProgram.programId,
+                })
+                .rpc();
+
+            const prop = await program.account.proposal.fetch(proposalPda);
+            expect(prop.threshold).to.equal(3);
+        });
+    });
+
+    // ------- pause -------
+    describe("pause", () => {
+        before(async () => {
+            await executeGovernance({
+                setGuardian: { guardian: signer3.publicKey },
+            });
+        });
+
+        it("guardian pauses the vault, blocking new transfers", async () => {
+            await program.methods
+                .pauseVault()
+                .accounts({
+                    guardian: signer3.publicKey,
+                    vaultConfig: vaultPda,
+                })
+                .signers([signer3])
+                .rpc();
+
+            const vault = await program.account.vaultConfig.fetch(vaultPda);
+            expect(vault.paused).to.be.true;
+
+            const id = await fetchProposalCount();
+            const [proposalPda] = getProposalPda(vaultPda, id);
+            try {
+                await program.methods
+                    .createProposal(
+                        recipient.publicKey,
+                        new BN(LAMPORTS_PER_SOL / 10),
+                        { sol: {} },
+                        "While paused",
+                        null,
+                        null,
+                        false,
+                    )
+                    .accounts({
+                        proposer: signer1.publicKey,
+                        vaultConfig: vaultPda,
+                        proposal: proposalPda,
+                        systemProgram: SystemProgram.programId,
+                    })
+                    .rpc();
+                expect.fail("should have rejected while paused");
+            } catch (err) {
+                expect(err.error.errorCode.code).to.equal("VaultPaused");
+            }
+        });
+
+        it("unpauses through a governance proposal", async () => {
+            await executeGovernance({ unpause: {} });
+
+            const vault = await program.account.vaultConfig.fetch(vaultPda);
+            expect(vault.paused).to.be.false;
+        });
+    });
+
+    // ------- veto_proposal -------
+    describe("veto_proposal", () => {
+        // Separate 1-of-2 vault so the time lock doesn't affect other tests.
+        const [vetoVaultPda] = getVaultPda(signer2.publicKey);
+
+        async function createOnVetoVault(
+            transferType: object,
+        ): Promise<PublicKey> {
+            const v = await program.account.vaultConfig.fetch(vetoVaultPda);
+            const [proposalPda] = getProposalPda(
+                vetoVaultPda,
+                v.proposalCount.toNumber(),
+            );
+            await program.methods
+                .createProposal(
+                    recipient.publicKey,
+                    new BN(LAMPORTS_PER_SOL / 10),
+                    transferType,
+                    "Veto vault proposal",
+                    null,
+                    null,
+                    false,
+                )
+                .accounts({
+                    proposer: signer2.publicKey,
+                    vaultConfig: vetoVaultPda,
+                    proposal: proposalPda,
+                    systemProgram: SystemProgram.programId,
+                })
+                .signers([signer2])
+                .rpc();
+            return proposalPda;
+        }
+
+        async function governOnVetoVault(action: object) {
+            const proposalPda = await createOnVetoVault({
+                governance: { action },
+            });
+            await program.methods
+                .executeGovernanceProposal()
+                .accounts({
+                    executor: signer2.publicKey,
+                    vaultConfig: vetoVaultPda,
+                    proposal: proposalPda,
+                    assetRegistry: null,
+                })
+                .signers([signer2])
+                .rpc();
+        }
+
+        before(async () => {
+            await program.methods
+                .initialize([signer2.publicKey, signer3.publicKey], 1)
+                .accounts({
+                    creator: signer2.publicKey,
+                    vaultConfig: vetoVaultPda,
+                    systemProgram: SystemProgram.programId,
+                })
+                .signers([signer2])
+                .rpc();
+
+            await governOnVetoVault({
+                addVetoMember: { member: nonSigner.publicKey },
+            });
+            await governOnVetoVault({
+                setTimelock: { timelockSecs: new BN(3600) },
+            });
+        });
+
+        it("veto council member vetoes an approved proposal", async () => {
+            const proposalPda = await createOnVetoVault({ sol: {} });
+
+            let prop = await program.account.proposal.fetch(proposalPda);
+            expect(prop.status).to.deep.equal({ approved: {} });
+
+            await program.methods
+                .vetoProposal()
+                .accounts({
+                    vetoer: nonSigner.publicKey,
+                    vaultConfig: vetoVaultPda,
+                    proposal: proposalPda,
+                })
+                .signers([nonSigner])
+                .rpc();
+
+            prop = await program.account.proposal.fetch(proposalPda);
+            expect(prop.status).to.deep.equal({ vetoed: {} });
+
+            try {
+                await program.methods
+                    .executeSolProposal()
+                    .accounts({
+                        executor: signer2.publicKey,
+                        vaultConfig: vetoVaultPda,
+                        proposal: proposalPda,
+                        recipient: recipient.publicKey,
+                        priceUpdate: null,
+                        systemProgram: SystemProgram.programId,
+                        policyProgram: null,
+                    })
+                    .signers([signer2])
+                    .rpc();
+                expect.fail("should not execute a vetoed proposal");
+            } catch (err) {
+                expect(err.error.errorCode.code).to.equal("ProposalVetoed");
+            }
+        });
+
+        it("rejects a time lock above the cap", async () => {
+            try {
+                // Main vault, which has no time lock of its own
+                await executeGovernance({
+                    setTimelock: {
+                        timelockSecs: new BN("9223372036854775807"),
+                    },
+                });
+                expect.fail("should have rejected the time lock");
+            } catch (err) {
+                expect(err.error.errorCode.code).to.equal("InvalidTimelock");
+            }
+        });
+    });
+
+    // ------- recovery -------
+    describe("recovery", () => {
+        const [recoveryPda] = PublicKey.findProgramAddressSync(
+            [Buffer.from("recovery"), vaultPda.toBuffer()],
+            program.programId,
+        );
+
+        before(async () => {
+            await executeGovernance({
+                setRecovery: {
+                    recovery: {
+                        keys: [nonSigner.publicKey],
+                        threshold: 1,
+                        delaySecs: new BN(7 * 24 * 60 * 60),
+                    },
+                },
+            });
+        });
+
+        it("recovery key initiates a signer-set replacement", async () => {
+            await program.methods
+                .initiateRecovery([nonSigner.publicKey, signer3.publicKey], 1)
+                .accounts({
+                    initiator: nonSigner.publicKey,
+                    vaultConfig: vaultPda,
+                    recoveryRequest: recoveryPda,
+                    systemProgram: SystemProgram.programId,
+                })
+                .signers([nonSigner])
+                .rpc();
+
+            const request =
+                await program.account.recoveryRequest.fetch(recoveryPda);
+            expect(request.newSigners).to.have.lengthOf(2);
+            expect(request.executableAt).to.not.be.null;
+        });
+
+        it("current signer cancels the pending recovery", async () => {
+            await program.methods
+                .cancelRecovery()
+                .accounts({
+                    signer: signer2.publicKey,
+                    vaultConfig: vaultPda,
+                    recoveryRequest: recoveryPda,
+                    initiator: nonSigner.publicKey,
+                })
+                .signers([signer2])
+                .rpc();
+
+            const info = await connection.getAccountInfo(recoveryPda);
+            expect(info).to.be.null;
+
+            const vault = await program.account.vaultConfig.fetch(vaultPda);
+            expect(vault.signers).to.have.lengthOf(3);
+        });
+
+        it("recovery keys cancel a request at the recovery threshold", async () => {
+            await program.methods
+                .initiateRecovery([nonSigner.publicKey], 1)
+                .accounts({
+                    initiator: nonSigner.publicKey,
+                    vaultConfig: vaultPda,
+                    recoveryRequest: recoveryPda,
+                    systemProgram: SystemProgram.programId,
+                })
+                .signers([nonSigner])
+                .rpc();
+
+            // The vault's recovery threshold is 1, so one key's vote is enough.
+            await program.methods
+                .cancelRecovery()
+                .accounts({
+                    signer: nonSigner.publicKey,
+                    vaultConfig: vaultPda,
+                    recoveryRequest: recoveryPda,
+                    initiator: nonSigner.publicKey,
+                })
+                .signers([nonSigner])
+                .rpc();
+
+            const info = await connection.getAccountInfo(recoveryPda);
+            expect(info).to.be.null;
+        });
+
+        it("executes an approved recovery after the delay", async function () {
+            // Separate 1-of-1 vault, so the main signer set is untouched.
+            const creator = Keypair.generate();
+            const keyA = Keypair.generate();
+            const keyB = Keypair.generate();
+            const [recoveryVaultPda] = getVaultPda(creator.publicKey);
+            const [requestPda] = PublicKey.findProgramAddressSync(
+                [Buffer.from("recovery"), recoveryVaultPda.toBuffer()],
+                program.programId,
+            );
+            await Promise.all([
+                airdrop(creator.publicKey, 1),
+                airdrop(keyA.publicKey, 1),
+            ]);
+
+            await program.methods
+                .initialize([creator.publicKey], 1)
+                .accounts({
+                    creator: creator.publicKey,
+                    vaultConfig: recoveryVaultPda,
+                    systemProgram: SystemProgram.programId,
+                })
+                .signers([creator])
+                .rpc();
+
+            const [proposalPda] = getProposalPda(recoveryVaultPda, 0);
+            await program.methods
+                .createProposal(
+                    recoveryVaultPda,
+                    new BN(0),
+                    {
+                        governance: {
+                            action: {
+                                setRecovery: {
+                                    recovery: {
+                                        keys: [keyA.publicKey, keyB.publicKey],
+                                        threshold: 2,
+                                        delaySecs: new BN(2),
+                                    },
+                                },
+                            },
+                        },
+                    },
+                    "Short recovery delay",
+                    null,
+                    null,
+                    false,
+                )
+                .accounts({
+                    proposer: creator.publicKey,
+                    vaultConfig: recoveryVaultPda,
+                    proposal: proposalPda,
+                    systemProgram: SystemProgram.programId,
+                })
+                .signers([creator])
+                .rpc();
+            try {
+                await program.methods
+                    .executeGovernanceProposal()
+                    .accounts({
+                        executor: creator.publicKey,
+                        vaultConfig: recoveryVaultPda,
+                        proposal: proposalPda,
+                        assetRegistry: null,
+                    })
+                    .signers([creator])
+                    .rpc();
+            } catch (err) {
+                // Only builds with the `short-recovery-delay` feature accept
+                // a delay under 7 days.
+                expect(err.error.errorCode.code).to.equal(
+                    "RecoveryDelayTooShort",
+                );
+                this.skip();
+            }
+
+            await program.methods
+                .initiateRecovery([keyA.publicKey], 1)
+                .accounts({
+                    initiator: keyA.publicKey,
+                    vaultConfig: recoveryVaultPda,
+                    recoveryRequest: requestPda,
+                    systemProgram: SystemProgram.programId,
+                })
+                .signers([keyA])
+                .rpc();
+            let request = await program.account.recoveryRequest.fetch(
+                requestPda,
+            );
+            expect(request.executableAt).to.be.null;
+
+            const execute = () =>
+                program.methods
+                    .executeRecovery()
+                    .accounts({
+                        executor: creator.publicKey,
+                        vaultConfig: recoveryVaultPda,
+                        recoveryRequest: requestPda,
+                        initiator: keyA.publicKey,
+                        systemProgram: SystemProgram.programId,
+                    })
+                    .signers([creator])
+                    .rpc();
+
+            try {
+                await execute();
+                expect.fail("should need a second approval");
+            } catch (err) {
+                expect(err.error.errorCode.code).to.equal(
+                    "RecoveryNotApproved",
+                );
+            }
+
+            await program.methods
+                .approveRecovery()
+                .accounts({
+                    recoveryKey: keyB.publicKey,
+                    vaultConfig: recoveryVaultPda,
+                    recoveryRequest: requestPda,
+                })
+                .signers([keyB])
+                .rpc();
+            request = await program.account.recoveryRequest.fetch(requestPda);
+            expect(request.executableAt).to.not.be.null;
+            expect(request.approvals).to.have.lengthOf(2);
+
+            try {
+                await execute();
+                expect.fail("should wait out the delay");
+            } catch (err) {
+                expect(err.error.errorCode.code).to.equal(
+                    "RecoveryDelayActive",
+                );
+            }
+
+            await new Promise((resolve) => setTimeout(resolve, 3000));
+            await execute();
+
+            const vault = await program.account.vaultConfig.fetch(
+                recoveryVaultPda,
+            );
+            expect(vault.signers.map((k) => k.toBase58())).to.deep.equal([
+                keyA.publicKey.toBase58(),
+            ]);
+            expect(vault.threshold).to.equal(1);
+            expect(await connection.getAccountInfo(requestPda)).to.be.null;
+        });
+    });
+
+    // ------- non-ATA token accounts -------
+    describe("non-ATA token accounts", () => {
+        it("pays into an existing non-ATA recipient token account", async () => {
+            const depositAccount = await createAccount(
+                connection,
+                wallet.payer,
+                testMint,
+                recipient.publicKey,
+                Keypair.generate(),
+            );
+            const amount = 1_000 * 10 ** TOKEN_DECIMALS;
+
+            const id = await fetchProposalCount();
+            const [proposalPda] = getProposalPda(vaultPda, id);
+            await program.methods
+                .createProposal(
+                    recipient.publicKey,
+                    new BN(amount),
+                    { splToken: { mint: testMint } },
+                    "Exchange deposit",
+                    null,
+                    null,
+                    false,
+                )
+                .accounts({
+                    proposer: signer1.publicKey,
+                    vaultConfig: vaultPda,
+                    proposal: proposalPda,
+                    systemProgram: SystemProgram.programId,
+                })
+                .rpc();
+            await program.methods
+                .approveProposal()
+                .accounts({
+                    signer: signer2.publicKey,
+                    vaultConfig: vaultPda,
+                    proposal: proposalPda,
+                })
+                .signers([signer2])
+                .rpc();
+
+            await program.methods
+                .executeTokenProposal()
+                .accounts({
+                    executor: signer1.publicKey,
+                    vaultConfig: vaultPda,
+                    proposal: proposalPda,
+                    recipient: recipient.publicKey,
+                    mint: testMint,
+                    streamReserve: getStreamReservePda(vaultPda, testMint)[0],
+                    vaultTokenAccount: getAssociatedTokenAddressSync(
+                        testMint,
+                        vaultPda,
+                        true,
+                    ),
+                    recipientAta: null,
+                    recipientTokenAccount: depositAccount,
+                    tokenProgram: TOKEN_PROGRAM_ID,
+                    associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+                    systemProgram: SystemProgram.programId,
+                    priceUpdate: null,
+                    policyProgram: null,
+                })
+                .rpc();
+
+            const acct = await getAccount(connection, depositAccount);
+            expect(Number(acct.amount)).to.equal(amount);
+        });
+
+        it("pays from a vault-owned non-ATA source account", async () => {
+            const amount = 500 * 10 ** TOKEN_DECIMALS;
+            const vaultSource = await createAccount(
+                connection,
+                wallet.payer,
+                testMint,
+                vaultPda,
+                Keypair.generate(),
+            );
+            await mintTo(
+                connection,
+                wallet.payer,
+                testMint,
+                vaultSource,
+                signer1,
+                amount,
+            );
+            const recipientAta = getAssociatedTokenAddressSync(
+                testMint,
+                recipient.publicKey,
+            );
+            const recipientBefore = await getAccount(connection, recipientAta);
+
+            const id = await fetchProposalCount();
+            const [proposalPda] = getProposalPda(vaultPda, id);
+            await program.methods
+                .createProposal(
+                    recipient.publicKey,
+                    new BN(amount),
+                    { splToken: { mint: testMint } },
+                    "Pay from side account",
+                    null,
+                    null,
+                    false,
+                )
+                .accounts({
+                    proposer: signer1.publicKey,
+                    vaultConfig: vaultPda,
+                    proposal: proposalPda,
+                    systemProgram: SystemProgram.programId,
+                })
+                .rpc();
+            await program.methods
+                .approveProposal()
+                .accounts({
+                    signer: signer2.publicKey,
+                    vaultConfig: vaultPda,
+                    proposal: proposalPda,
+                })
+                .signers([signer2])
+                .rpc();
+
+            await program.methods
+                .executeTokenProposal()
+                .accounts({
+                    executor: signer1.publicKey,
+                    vaultConfig: vaultPda,
+                    proposal: proposalPda,
+                    recipient: recipient.publicKey,
+                    mint: testMint,
+                    streamReserve: getStreamReservePda(vaultPda, testMint)[0],
+                    vaultTokenAccount: vaultSource,
+                    recipientAta,
+                    recipientTokenAccount: null,
+                    tokenProgram: TOKEN_PROGRAM_ID,
+                    associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+                    systemProgram: SystemProgram.programId,
+                    priceUpdate: null,
+                    policyProgram: null,
+                })
+                .rpc();
+
+            const source = await getAccount(connection, vaultSource);
+            expect(Number(source.amount)).to.equal(0);
+            const recipientAfter = await getAccount(connection, recipientAta);
+            expect(
+                Number(recipientAfter.amount) - Number(recipientBefore.amount),
+            ).to.equal(amount);
+        });
+    });
+
+    // ------- staking -------
+    describe("staking", () => {
+        const STAKE_CONFIG = new PublicKey(
+            "StakeConfig11111111111111111111111111111111",
+        );
+        let voteAccount: PublicKey;
+        let stakePda: PublicKey;
+
+        async function createStakeProposal(
+            transferType: object,
+            amount: number,
+        ): Promise<PublicKey> {
+            const id = await fetchProposalCount();
+            const [proposalPda] = getProposalPda(vaultPda, id);
+            await program.methods
+                .createProposal(
+                    vaultPda,
+                    new BN(amount),
+                    transferType,
+                    "Stake",
+                    null,
+                    null,
+                    false,
+                )
+                .accounts({
+                    proposer: signer1.publicKey,
+                    vaultConfig: vaultPda,
+                    proposal: proposalPda,
+                    systemProgram: SystemProgram.programId,
+                })
+                .rpc();
+            await program.methods
+                .approveProposal()
+                .accounts({
+                    signer: signer2.publicKey,
+                    vaultConfig: vaultPda,
+                    proposal: proposalPda,
+                })
+                .signers([signer2])
+                .rpc();
+            return proposalPda;
+        }
+
+        async function executeStake(
+            proposalPda: PublicKey,
+            stakeAccount: PublicKey,
+            vote: PublicKey | null,
+        ) {
+            await program.methods
+                .executeStakeProposal()
+                .accounts({
+                    executor: signer1.publicKey,
+                    vaultConfig: vaultPda,
+                    proposal: proposalPda,
+                    stakeAccount,
+                    voteAccount: vote,
+                    stakeHistory: SYSVAR_STAKE_HISTORY_PUBKEY,
+                    stakeConfig: STAKE_CONFIG,
+                    stakeProgram: StakeProgram.programId,
+                    clock: SYSVAR_CLOCK_PUBKEY,
+                    rent: SYSVAR_RENT_PUBKEY,
+                    systemProgram: SystemProgram.programId,
+                    policyProgram: null,
+                })
+                .rpc();
+        }
+
+        async function fetchDeactivationEpoch(): Promise<string> {
+            const info = await connection.getParsedAccountInfo(stakePda);
+            const parsed = (info.value.data as any).parsed;
+            return parsed.info.stake.delegation.deactivationEpoch;
+        }
+
+        it("creates and delegates a vault stake account", async () => {
+            await program.methods
+                .depositSol(new BN(2 * LAMPORTS_PER_SOL))
+                .accounts({
+                    depositor: signer1.publicKey,
+                    vaultConfig: vaultPda,
+                    depositRecord: getDepositRecordPda(
+                        vaultPda,
+                        signer1.publicKey,
+                        PublicKey.default,
+                    )[0],
+                    systemProgram: SystemProgram.programId,
+                })
+                .rpc();
+
+            const { current } = await connection.getVoteAccounts();
+            voteAccount = new PublicKey(current[0].votePubkey);
+
+            const proposalPda = await createStakeProposal(
+                { createStake: { voteAccount } },
+                LAMPORTS_PER_SOL,
+            );
+            [stakePda] = PublicKey.findProgramAddressSync(
+                [Buffer.from("stake"), proposalPda.toBuffer()],
+                program.programId,
+            );
+            await executeStake(proposalPda, stakePda, voteAccount);
+
+            const stakeInfo = await connection.getAccountInfo(stakePda);
+            expect(stakeInfo.owner.equals(StakeProgram.programId)).to.be.true;
+
+            const prop = await program.account.proposal.fetch(proposalPda);
+            expect(prop.status).to.deep.equal({ executed: {} });
+        });
+
+        it("rejects a stake account other than the proposal's", async () => {
+            const proposalPda = await createStakeProposal(
+                { deactivateStake: { stakeAccount: stakePda } },
+                0,
+            );
+            try {
+                await executeStake(
+                    proposalPda,
+                    Keypair.generate().publicKey,
+                    null,
+                );
+                expect.fail("should have rejected the stake account");
+            } catch (err) {
+                expect(err.error.errorCode.code).to.equal(
+                    "InvalidStakeAccount",
+                );
+            }
+        });
+
+        it("deactivates a vault stake account", async () => {
+            const proposalPda = await createStakeProposal(
+                { deactivateStake: { stakeAccount: stakePda } },
+                0,
+            );
+            await executeStake(proposalPda, stakePda, null);
+
+            expect(await fetchDeactivationEpoch()).to.not.equal(
+                "18446744073709551615",
+            );
+        });
+
+        it("rejects a vote account other than the proposal's", async () => {
+            const proposalPda = await createStakeProposal(
+                { delegateStake: { stakeAccount: stakePda, voteAccount } },
+                0,
+            );
+            for (const vote of [null, Keypair.generate().publicKey]) {
+                try {
+                    await executeStake(proposalPda, stakePda, vote);
+                    expect.fail("should have rejected the vote account");
+                } catch (err) {
+                    expect(err.error.errorCode.code).to.equal(
+                        "InvalidVoteAccount",
+                    );
+                }
+            }
+        });
+
+        it("re-delegates a deactivated vault stake account", async () => {
+            const proposalPda = await createStakeProposal(
+                { delegateStake: { stakeAccount: stakePda, voteAccount } },
+                0,
+            );
+            await executeStake(proposalPda, stakePda, voteAccount);
+
+            expect(await fetchDeactivationEpoch()).to.equal(
+                "18446744073709551615",
+            );
+            const prop = await program.account.proposal.fetch(proposalPda);
+            expect(prop.status).to.deep.equal({ executed: {} });
+        });
+
+        it("withdraws unstaked lamports back into the vault", async () => {
+            // Lamports sent to a delegated stake account stay unstaked and
+            // can be withdrawn without waiting for deactivation.
+            const extra = LAMPORTS_PER_SOL / 10;
+            await provider.sendAndConfirm(
+                new Transaction().add(
+                    SystemProgram.transfer({
+                        fromPubkey: wallet.publicKey,
+                        toPubkey: stakePda,
+                        lamports: extra,
+                    }),
+                ),
+            );
+
+            const proposalPda = await createStakeProposal(
+                { withdrawStake: { stakeAccount: stakePda } },
+                extra,
+            );
+            const vaultBefore = await connection.getBalance(vaultPda);
+            await executeStake(proposalPda, stakePda, null);
+
+            const vaultAfter = await connection.getBalance(vaultPda);
+            expect(vaultAfter - vaultBefore).to.equal(extra);
+        });
+    });
+
+    // ------- wrap / unwrap SOL -------
+    describe("wrap / unwrap SOL", () => {
+        const wsolAta = getAssociatedTokenAddressSync(
+            NATIVE_MINT,
+            vaultPda,
+            true,
+        );
+        const [wsolReservePda] = getStreamReservePda(vaultPda, NATIVE_MINT);
+
+        async function createApproved(
+            recipientKey: PublicKey,
+            amount: number,
+            transferType: object,
+        ): Promise<PublicKey> {
+            const id = await fetchProposalCount();
+            const [proposalPda] = getProposalPda(vaultPda, id);
+            await program.methods
+                .createProposal(
+                    recipientKey,
+                    new BN(amount),
+                    transferType,
+                    "wSOL stream",
+                    null,
+                    null,
+                    false,
+                )
+                .accounts({
+                    proposer: signer1.publicKey,
+                    vaultConfig: vaultPda,
+                    proposal: proposalPda,
+                    systemProgram: SystemProgram.programId,
+                })
+                .rpc();
+            await program.methods
+                .approveProposal()
+                .accounts({
+                    signer: signer2.publicKey,
+                    vaultConfig: vaultPda,
+                    proposal: proposalPda,
+                })
+                .signers([signer2])
+                .rpc();
+            return proposalPda;
+        }
+
+        async function executeWrap(transferType: object, amount: number) {
+            const id = await fetchProposalCount();
+            const [proposalPda] = getProposalPda(vaultPda, id);
+
+            await program.methods
+                .createProposal(
+                    vaultPda,
+                    new BN(amount),
+                    transferType,
+                    "Wrap",
+                    null,
+                    null,
+                    false,
+                )
+                .accounts({
+                    proposer: signer1.publicKey,
+                    vaultConfig: vaultPda,
+                    proposal: proposalPda,
+                    systemProgram: SystemProgram.programId,
+                })
+                .rpc();
+            await program.methods
+                .approveProposal()
+                .accounts({
+                    signer: signer2.publicKey,
+                    vaultConfig: vaultPda,
+                    proposal: proposalPda,
+                })
+                .signers([signer2])
+                .rpc();
+            await program.methods
+                .executeWrapProposal()
+                .accounts({
+                    executor: signer1.publicKey,
+                    vaultConfig: vaultPda,
+                    proposal: proposalPda,
+                    nativeMint: NATIVE_MINT,
+                    vaultWsolAta: wsolAta,
+                    streamReserve: wsolReservePda,
+                    tokenProgram: TOKEN_PROGRAM_ID,
+                    associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+                    systemProgram: SystemProgram.programId,
+                    policyProgram: null,
+                })
+                .rpc();
+        }
+
+        it("wraps vault lamports into wSOL", async () => {
+            await executeWrap({ wrapSol: {} }, LAMPORTS_PER_SOL / 10);
+
+            const acct = await getAccount(connection, wsolAta);
+            expect(Number(acct.amount)).to.equal(LAMPORTS_PER_SOL / 10);
+        });
+
+        it("refuses to unwrap wSOL reserved for a stream", async () => {
+            // Not yet vested, so cancelling it releases the whole reserve.
+            const now = Math.floor(Date.now() / 1000);
+            const streamProposalPda = await createApproved(
+                recipient.publicKey,
+                LAMPORTS_PER_SOL / 20,
+                {
+                    stream: {
+                        mint: NATIVE_MINT,
+                        startTs: new BN(now + 3600),
+                        cliffTs: new BN(now + 3600),
+                        endTs: new BN(now + 7200),
+                    },
+                },
+            );
+            const [streamPda] = PublicKey.findProgramAddressSync(
+                [Buffer.from("stream"), streamProposalPda.toBuffer()],
+                program.programId,
+            );
+            await program.methods
+                .executeStreamProposal()
+                .accounts({
+                    executor: signer1.publicKey,
+                    vaultConfig: vaultPda,
+                    proposal: streamProposalPda,
+                    stream: streamPda,
+                    mint: NATIVE_MINT,
+                    vaultAta: wsolAta,
+                    streamReserve: wsolReservePda,
+                    priceUpdate: null,
+                    systemProgram: SystemProgram.programId,
+                    policyProgram: null,
+                })
+                .rpc();
+
+            try {
+                await executeWrap({ unwrapSol: {} }, 0);
+                expect.fail("should not unwrap reserved wSOL");
+            } catch (err) {
+                expect(err.error.errorCode.code).to.equal(
+                    "StreamFundsReserved",
+                );
+            }
+
+            const cancelProposalPda = await createApproved(
+                recipient.publicKey,
+                0,
+                { cancelStream: { stream: streamPda } },
+            );
+            await program.methods
+                .cancelStream()
+                .accounts({
+                    executor: signer1.publicKey,
+                    vaultConfig: vaultPda,
+                    proposal: cancelProposalPda,
+                    stream: streamPda,
+                    streamReserve: wsolReservePda,
+                    vaultAta: wsolAta,
+                    policyProgram: null,
+                })
+                .rpc();
+
+            const reserve =
+                await program.account.streamReserve.fetch(wsolReservePda);
+            expect(reserve.reserved.toNumber()).to.equal(0);
+        });
+
+        it("unwraps wSOL back into vault lamports", async () => {
+            const vaultBefore = await connection.getBalance(vaultPda);
+            const ataLamports = (await connection.getAccountInfo(wsolAta))
+                .lamports;
+            await executeWrap({ unwrapSol: {} }, 0);
+
+            const info = await connection.getAccountInfo(wsolAta);
+            expect(info).to.be.null;
+
+            // The ATA's wrapped lamports and rent both stay in the vault
+            const vaultAfter = await connection.getBalance(vaultPda);
+            expect(vaultAfter - vaultBefore).to.equal(ataLamports);
+        });
+    });
+
+    // ------- share pool -------
+    describe("share pool", () => {
+        const holder = Keypair.generate();
+        const [shareMint] = PublicKey.findProgramAddressSync(
+            [Buffer.from("share_mint"), vaultPda.toBuffer()],
+            program.programId,
+        );
+        const holderShareAta = getAssociatedTokenAddressSync(
+            shareMint,
+            holder.publicKey,
+        );
+
+        before(async () => {
+            await airdrop(holder.publicKey, 5);
+        });
+
+        it("creates the share pool via proposal", async () => {
+            const id = await fetchProposalCount();
+            const [proposalPda] = getProposalPda(vaultPda, id);
+
+            await program.methods
+                .createProposal(
+                    vaultPda,
+                    new BN(0),
+                    {
+                        createSharePool: {
+                            autoApproveMaxShares: new BN(LAMPORTS_PER_SOL),
+                        },
+                    },
+                    "Open share pool",
+                    null,
+                    null,
+                    false,
+                )
+                .accounts({
+                    proposer: signer1.publicKey,
+                    vaultConfig: vaultPda,
+                    proposal: proposalPda,
+                    systemProgram: SystemProgram.programId,
+                })
+                .rpc();
+            await program.methods
+                .approveProposal()
+                .accounts({
+                    signer: signer2.publicKey,
+                    vaultConfig: vaultPda,
+                    proposal: proposalPda,
+                })
+                .signers([signer2])
+                .rpc();
+            await program.methods
+                .executeSharePoolProposal()
+                .accounts({
+                    executor: signer1.publicKey,
+                    vaultConfig: vaultPda,
+                    proposal: proposalPda,
+                    shareMint,
+                    tokenProgram: TOKEN_PROGRAM_ID,
+                    systemProgram: SystemProgram.programId,
+                })
+                .rpc();
+
+            const vault = await program.account.vaultConfig.fetch(vaultPda);
+            expect(vault.sharePool.mint.equals(shareMint)).to.be.true;
+            // SOL already in the vault stays outside the pool
+            expect(vault.sharePool.poolLamports.toNumber()).to.equal(0);
+        });
+
+        it("mints shares for a SOL deposit", async () => {
+            await program.methods
+                .depositForShares(new BN(2 * LAMPORTS_PER_SOL))
+                .accounts({
+                    depositor: holder.publicKey,
+                    vaultConfig: vaultPda,
+                    shareMint,
+                    depositorShareAta: holderShareAta,
+                    depositRecord: getDepositRecordPda(
+                        vaultPda,
+                        holder.publicKey,
+                        PublicKey.default,
+                    )[0],
+                    tokenProgram: TOKEN_PROGRAM_ID,
+                    associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+                    systemProgram: SystemProgram.programId,
+                })
+                .signers([holder])
+                .rpc();
+
+            const acct = await getAccount(connection, holderShareAta);
+            expect(Number(acct.amount)).to.equal(2 * LAMPORTS_PER_SOL);
+        });
+
+        // Vault lamports proposals may spend: the balance above rent, less
+        // the share pool's lamports.
+        async function fetchSpendable(): Promise<number> {
+            const info = await connection.getAccountInfo(vaultPda);
+            const rent = await connection.getMinimumBalanceForRentExemption(
+                info.data.length,
+            );
+            const vault = await program.account.vaultConfig.fetch(vaultPda);
+            return (
+                info.lamports - rent - vault.sharePool.poolLamports.toNumber()
+            );
+        }
+
+        async function createApproved(
+            recipientKey: PublicKey,
+            amount: number,
+            transferType: object,
+        ): Promise<PublicKey> {
+            const id = await fetchProposalCount();
+            const [proposalPda] = getProposalPda(vaultPda, id);
+            await program.methods
+                .createProposal(
+                    recipientKey,
+                    new BN(amount),
+                    transferType,
+                    "Spends pooled SOL",
+                    null,
+                    null,
+                    false,
+                )
+                .accounts({
+                    proposer: signer1.publicKey,
+                    vaultConfig: vaultPda,
+                    proposal: proposalPda,
+                    systemProgram: SystemProgram.programId,
+                })
+                .rpc();
+            // Large SOL amounts fall in the 3-signer tier.
+            for (const s of [signer2, signer3]) {
+                const prop = await program.account.proposal.fetch(proposalPda);
+                if (!("active" in prop.status)) break;
+                await program.methods
+                    .approveProposal()
+                    .accounts({
+                        signer: s.publicKey,
+                        vaultConfig: vaultPda,
+                        proposal: proposalPda,
+                    })
+                    .signers([s])
+                    .rpc();
+            }
+            return proposalPda;
+        }
+
+        async function expectInsufficient(execute: Promise<unknown>) {
+            try {
+                await execute;
+                expect.fail("should not spend pooled lamports");
+            } catch (err) {
+                expect(err.error.errorCode.code).to.equal(
+                    "InsufficientBalance",
+                );
+            }
+        }
+
+        it("keeps pooled lamports out of SOL proposals", async () => {
+            const proposalPda = await createApproved(
+                recipient.publicKey,
+                (await fetchSpendable()) + 1,
+                { sol: {} },
+            );
+            await expectInsufficient(
+                program.methods
+                    .executeSolProposal()
+                    .accounts({
+                        executor: signer1.publicKey,
+                        vaultConfig: vaultPda,
+                        proposal: proposalPda,
+                        recipient: recipient.publicKey,
+                        priceUpdate: null,
+                        systemProgram: SystemProgram.programId,
+                        policyProgram: null,
+                    })
+                    .rpc(),
+            );
+        });
+
+        it("keeps pooled lamports out of new stake accounts", async () => {
+            const { current } = await connection.getVoteAccounts();
+            const voteAccount = new PublicKey(current[0].votePubkey);
+            // Stake rent comes on top of the amount.
+            const proposalPda = await createApproved(
+                vaultPda,
+                await fetchSpendable(),
+                { createStake: { voteAccount } },
+            );
+            const [stakePda] = PublicKey.findProgramAddressSync(
+                [Buffer.from("stake"), proposalPda.toBuffer()],
+                program.programId,
+            );
+            await expectInsufficient(
+                program.methods
+                    .executeStakeProposal()
+                    .accounts({
+                        executor: signer1.publicKey,
+                        vaultConfig: vaultPda,
+                        proposal: proposalPda,
+                        stakeAccount: stakePda,
+                        voteAccount,
+                        stakeHistory: SYSVAR_STAKE_HISTORY_PUBKEY,
+                        stakeConfig: new PublicKey(
+                            "StakeConfig11111111111111111111111111111111",
+                        ),
+                        stakeProgram: StakeProgram.programId,
+                        clock: SYSVAR_CLOCK_PUBKEY,
+                        rent: SYSVAR_RENT_PUBKEY,
+                        systemProgram: SystemProgram.programId,
+                        policyProgram: null,
+                    })
+                    .rpc(),
+            );
+        });
+
+        it("keeps pooled lamports out of wrapped SOL", async () => {
+            const proposalPda = await createApproved(
+                vaultPda,
+                (await fetchSpendable()) + 1,
+                { wrapSol: {} },
+            );
+            await expectInsufficient(
+                program.methods
+                    .executeWrapProposal()
+                    .accounts({
+                        executor: signer1.publicKey,
+                        vaultConfig: vaultPda,
+                        proposal: proposalPda,
+                        nativeMint: NATIVE_MINT,
+                        vaultWsolAta: getAssociatedTokenAddressSync(
+                            NATIVE_MINT,
+                            vaultPda,
+                            true,
+                        ),
+                        streamReserve: getStreamReservePda(
+                            vaultPda,
+                            NATIVE_MINT,
+                        )[0],
+                        tokenProgram: TOKEN_PROGRAM_ID,
+                        associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+                        systemProgram: SystemProgram.programId,
+                        policyProgram: null,
+                    })
+                    .rpc(),
+            );
+        });
+
+        it("redeems shares within the auto-approval limit", async () => {
+            const before = await connection.getBalance(holder.publicKey);
+
+            await program.methods
+                .redeemShares(new BN(LAMPORTS_PER_SOL / 2))
+                .accounts({
+                    holder: holder.publicKey,
+                    vaultConfig: vaultPda,
+                    shareMint,
+                    holderShareAccount: holderShareAta,
+                    tokenProgram: TOKEN_PROGRAM_ID,
+                    policyProgram: null,
+                })
+                .signers([holder])
+                .rpc();
+
+            const after = await connection.getBalance(holder.publicKey);
+            expect(after).to.be.greaterThan(before);
+        });
+
+        it("counts auto-approved redemptions across calls", async () => {
+            try {
+                await program.methods
+                    .redeemShares(new BN(LAMPORTS_PER_SOL / 2 + 1))
+                    .accounts({
+                        holder: holder.publicKey,
+                        vaultConfig: vaultPda,
+                        shareMint,
+                        holderShareAccount: holderShareAta,
+                        tokenProgram: TOKEN_PROGRAM_ID,
+                        policyProgram: null,
+                    })
+                    .signers([holder])
+                    .rpc();
+                expect.fail("should have failed");
+            } catch (err) {
+                expect(err.error.errorCode.code).to.equal(
+                    "RedemptionNeedsApproval",
+                );
+            }
+
+            const vault = await program.account.vaultConfig.fetch(vaultPda);
+            expect(vault.sharePool.redeemedInWindow.toNumber()).to.equal(
+                LAMPORTS_PER_SOL / 2,
+            );
+        });
+
+        it("rejects large redemptions without approval", async () => {
+            try {
+                await program.methods
+                    .redeemShares(new BN(LAMPORTS_PER_SOL + 1))
+                    .accounts({
+                        holder: holder.publicKey,
+                        vaultConfig: vaultPda,
+                        shareMint,
+                        holderShareAccount: holderShareAta,
+                        tokenProgram: TOKEN_PROGRAM_ID,
+                        policyProgram: null,
+                    })
+                    .signers([holder])
+                    .rpc();
+                expect.fail("should have failed");
+            } catch (err) {
+                expect(err.error.errorCode.code).to.equal(
+                    "RedemptionNeedsApproval",
+                );
+            }
+        });
+
+        it("redeems the rest through an approved request", async () => {
+            const acct = await getAccount(connection, holderShareAta);
+            const shares = new BN(acct.amount.toString());
+            const id = await fetchProposalCount();
+            const [proposalPda] = getProposalPda(vaultPda, id);
+
+            await program.methods
+                .requestRedemption(shares)
+                .accounts({
+                    holder: holder.publicKey,
+                    vaultConfig: vaultPda,
+                    shareMint,
+                    holderShareAccount: holderShareAta,
+                    proposal: proposalPda,
+                    systemProgram: SystemProgram.programId,
+                })
+                .signers([holder])
+                .rpc();
+            for (const s of [signer1, signer2]) {
+                await program.methods
+                    .approveProposal()
+                    .accounts({
+                        signer: s.publicKey,
+                        vaultConfig: vaultPda,
+                        proposal: proposalPda,
+                    })
+                    .signers([s])
+                    .rpc();
+            }
+            await program.methods
+                .executeRedemptionProposal()
+                .accounts({
+                    holder: holder.publicKey,
+                    vaultConfig: vaultPda,
+                    proposal: proposalPda,
+                    shareMint,
+                    holderShareAccount: holderShareAta,
+                    tokenProgram: TOKEN_PROGRAM_ID,
+                    policyProgram: null,
+                })
+                .signers([holder])
+                .rpc();
+
+            const remaining = await getAccount(connection, holderShareAta);
+            expect(Number(remaining.amount)).to.equal(0);
+        });
+    });
+
+    // ------- deposit policy -------
+    describe("deposit policy", () => {
+        const outsider = Keypair.generate();
+
+        function depositSol(depositor: Keypair) {
+            return program.methods
+                .depositSol(new BN(LAMPORTS_PER_SOL / 10))
+                .accounts({
+                    depositor: depositor.publicKey,
+                    vaultConfig: vaultPda,
+                    depositRecord: getDepositRecordPda(
+                        vaultPda,
+                        depositor.publicKey,
+                        PublicKey.default,
+                    )[0],
+                    systemProgram: SystemProgram.programId,
+                })
+                .signers([depositor])
+                .rpc();
+        }
+
+        before(async () => {
+            await airdrop(outsider.publicKey, 1);
+            await executeGovernance({
+                addAllowedDepositor: { depositor: signer1.publicKey },
+            });
+            await executeGovernance({
+                setDepositRestrictions: {
+                    restrictMints: false,
+                    restrictDepositors: true,
+                },
+            });
+        });
+
+        it("accepts deposits from allowed depositors", async () => {
+            await depositSol(signer1);
+        });
+
+        it("rejects deposits from other depositors", async () => {
+            try {
+                await depositSol(outsider);
+                expect.fail("should have failed");
+            } catch (err) {
+                expect(err.error.errorCode.code).to.equal(
+                    "DepositorNotAllowed",
+                );
+            }
+        });
+
+        after(async () => {
+            await executeGovernance({
+                setDepositRestrictions: {
+                    restrictMints: false,
+                    restrictDepositors: false,
+                },
+            });
+        });
+    });
+
+    // ------- asset registry -------
+    describe("asset registry", () => {
+        it("removes a tracked mint via governance", async () => {
+            const [registryPda] = getAssetRegistryPda(vaultPda);
+            await executeGovernance(
+                { removeTrackedAsset: { mint: testMint } },
+                registryPda,
+            );
+
+            const registry =
+                await program.account.assetRegistry.fetch(registryPda);
+            expect(registry.assets).to.have.lengthOf(0);
+        });
+    });
+
+    // ------- treasury valuation -------
+    describe("get_treasury_value", () => {
+        it("returns zero when no price feeds are configured", async () => {
+            const value = await program.methods
+                .getTreasuryValue(new BN(60))
+                .accounts({
+                    vaultConfig: vaultPda,
+                    assetRegistry: getAssetRegistryPda(vaultPda)[0],
+                    solPriceUpdate: null,
+                })
+                .view();
+            expect(value.toNumber()).to.equal(0);
+        });
+
+        it("requires a pair for every priced asset", async () => {
+            const [registryPda] = getAssetRegistryPda(vaultPda);
+            await program.methods
+                .depositToken(new BN(1))
+                .accounts({
+                    depositor: signer1.publicKey,
+                    vaultConfig: vaultPda,
+                    mint: testMint,
+                    depositorAta: getAssociatedTokenAddressSync(
+                        testMint,
+                        signer1.publicKey,
+                    ),
+                    vaultAta: getAssociatedTokenAddressSync(
+                        testMint,
+                        vaultPda,
+                        true,
+                    ),
+                    depositRecord: getDepositRecordPda(
+                        vaultPda,
+                        signer1.publicKey,
+                        testMint,
+                    )[0],
+                    assetRegistry: registryPda,
+                    tokenProgram: TOKEN_PROGRAM_ID,
+                    associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+                    systemProgram: SystemProgram.programId,
+                })
+                .rpc();
+            const feedId = Array.from(Keypair.generate().publicKey.toBytes());
+            await executeGovernance(
+                { setAssetPriceFeed: { mint: testMint, feedId } },
+                registryPda,
+            );
+
+            try {
+                await program.methods
+                    .getTreasuryValue(new BN(60))
+                    .accounts({
+                        vaultConfig: vaultPda,
+                        assetRegistry: registryPda,
+                        solPriceUpdate: null,
+                    })
+                    .rpc();
+                expect.fail("should have failed");
+            } catch (err) {
+                expect(err.error.errorCode.code).to.equal(
+                    "InvalidValuationAccounts",
+                );
+            }
+        });
+    });
+
+    // ------- account migration -------
+    describe("migration", () => {
+        // Pre-versioning accounts loaded from tests/fixtures; see
+        // legacy_accounts.py for their contents.
+        const legacyVault = new PublicKey(
+            "BRKD6AFTV3Gs2mx14mZucGf1LYpJGDrs9F7ouHvZe1KF",
+        );
+        const legacyProposal = new PublicKey(
+            "DF7MMnrofArhkedsL6UCsNriFNYREMez8tZAkDD6Daz6",
+        );
+        const legacyVaultCopy = new PublicKey(
+            "3Lxdp8Wr6gh8wSPZFRgbn5iuUMfJa38S4F6JvFauR9g7",
+        );
+        const legacyProposalCopy = new PublicKey(
+            "6emwRfAJAVpvczu1SSRuveq327Rzcas4wZQzgV3zVgn5",
+        );
+        const legacySigners = [
+            "24gtonSUcXFmsDqg6aGH3NABhWBavjjtLtvsCMCqpTBG",
+            "BYJ7zQPrELDQjNWEbAqCA65tThpUCYCyXks9iETjv2oo",
+            "EMUZpY4Km1WReigADP2eVkgcH8ADnuUz19bdjms64e5o",
+        ].map((k) => new PublicKey(k));
+
+        async function expectError(call: Promise<unknown>, code: string) {
+            try {
+                await call;
+                expect.fail(`should have failed with ${code}`);
+            } catch (err) {
+                expect(err.error.errorCode.code).to.equal(code);
+            }
+        }
+
+        function migrateVault(vaultConfig: PublicKey) {
+            return program.methods
+                .migrateVault()
+                .accounts({
+                    payer: signer1.publicKey,
+                    vaultConfig,
+                    systemProgram: SystemProgram.programId,
+                })
+                .rpc();
+        }
+
+        function migrateProposal(proposal: PublicKey) {
+            return program.methods
+                .migrateProposal()
+                .accounts({
+                    payer: signer1.publicKey,
+                    vaultConfig: legacyVault,
+                    proposal,
+                    systemProgram: SystemProgram.programId,
+                })
+                .rpc();
+        }
+
+        it("reports a legacy vault as not migrated", async () => {
+            await expectError(
+                program.methods
+                    .depositSol(new BN(LAMPORTS_PER_SOL / 100))
+                    .accounts({
+                        depositor: signer1.publicKey,
+                        vaultConfig: legacyVault,
+                        depositRecord: getDepositRecordPda(
+                            legacyVault,
+                            signer1.publicKey,
+                            PublicKey.default,
+                        )[0],
+                        systemProgram: SystemProgram.programId,
+                    })
+                    .rpc(),
+                "AccountNotMigrated",
+            );
+            await expectError(
+                migrateProposal(legacyProposal),
+                "AccountNotMigrated",
+            );
+        });
+
+        it("rejects a legacy vault outside its PDA", async () => {
+            await expectError(
+                migrateVault(legacyVaultCopy),
+                "InvalidLegacyAccount",
+            );
+        });
+
+        it("migrates a legacy vault in place", async () => {
+            const before = await connection.getAccountInfo(legacyVault);
+            await migrateVault(legacyVault);
+
+            const after = await connection.getAccountInfo(legacyVault);
+            expect(after.data.length).to.be.greaterThan(before.data.length);
+
+            const vault = await program.account.vaultConfig.fetch(legacyVault);
+            expect(vault.version).to.equal(1);
+            expect(vault.signers.map((k) => k.toBase58())).to.deep.equal(
+                legacySigners.map((k) => k.toBase58()),
+            );
+            expect(vault.threshold).to.equal(2);
+            expect(vault.cancelThreshold).to.equal(2);
+            expect(vault.proposalCount.toNumber()).to.equal(1);
+
+            await expectError(migrateVault(legacyVault), "AlreadyMigrated");
+        });
+
+        it("reports a legacy proposal as not migrated", async () => {
+            await expectError(
+                program.methods
+                    .approveProposal()
+                    .accounts({
+                        signer: signer1.publicKey,
+                        vaultConfig: legacyVault,
+                        proposal: legacyProposal,
+                    })
+                    .rpc(),
+                "AccountNotMigrated",
+            );
+        });
+
+        it("rejects a legacy proposal outside its PDA", async () => {
+            await expectError(
+                migrateProposal(legacyProposalCopy),
+                "InvalidLegacyAccount",
+            );
+        });
+
+        it("migrates a legacy proposal and maps its votes to bitmaps", async () => {
+            await migrateProposal(legacyProposal);
+
+            const prop = await program.account.proposal.fetch(legacyProposal);
+            expect(prop.version).to.equal(1);
+            expect(prop.description).to.equal("Legacy payout");
+            expect(prop.amount.toNumber()).to.equal(LAMPORTS_PER_SOL);
+            expect(prop.status).to.deep.equal({ active: {} });
+            expect(prop.threshold).to.equal(2);
+            // Signers 0 and 2 approved; the third approval came from a key
+            // that is not a signer and is dropped.
+            expect(
+                decodeVotes(prop.approvalBitmap, legacySigners),
+            ).to.deep.equal([legacySigners[0], legacySigners[2]]);
+            expect(
+                decodeVotes(prop.rejectionBitmap, legacySigners),
+            ).to.deep.equal([legacySigners[1]]);
+
+            await expectError(
+                migrateProposal(legacyProposal),
+                "AlreadyMigrated",
+            );
+        });
+
+        it("creates accounts on the current layout version", async () => {
+            const vault = await program.account.vaultConfig.fetch(vaultPda);
+            expect(vault.version).to.equal(1);
+        });
+
+        it("rejects migrating a current vault", async () => {
+            try {
+                await program.methods
+                    .migrateVault()
+                    .accounts({
+                        payer: signer1.publicKey,
+                        vaultConfig: vaultPda,
+                        systemProgram: SystemProgram.programId,
+                    })
+                    .rpc();
+                expect.fail("should have failed");
+            } catch (err) {
+                expect(err.error.errorCode.code).to.equal("AlreadyMigrated");
+            }
+        });
+    });
+
+    // ------- dynamic signer list -------
+    describe("signer management", () => {
+        const newSigner = Keypair.generate();
+
+        it("adds a signer and grows the vault account", async () => {
+            const before = await connection.getAccountInfo(vaultPda);
+            await executeGovernance({
+                addSigner: { signer: newSigner.publicKey },
+            });
+
+            const after = await connection.getAccountInfo(vaultPda);
+            expect(after.data.length).to.equal(before.data.length + 32);
+            const vault = await program.account.vaultConfig.fetch(vaultPda);
+            expect(vault.signers).to.have.lengthOf(4);
+        });
+
+        it("removes a signer and shrinks the vault account", async () => {
+            const before = await connection.getAccountInfo(vaultPda);
+            await executeGovernance({
+                removeSigner: { signer: newSigner.publicKey },
+            });
+
+            const after = await connection.getAccountInfo(vaultPda);
+            expect(after.data.length).to.equal(before.data.length - 32);
+            const vault = await program.account.vaultConfig.fetch(vaultPda);
+            expect(vault.signers).to.have.lengthOf(3);
+        });
+
+        it("rejects removing a signer a threshold tier needs", async () => {
+            // The 3-approval SOL tier from the tier tests is still set
+            try {
+                await executeGovernance({
+                    removeSigner: { signer: signer3.publicKey },
+                });
+                expect.fail("should have failed");
+            } catch (err) {
+                expect(err.error.errorCode.code).to.equal("InvalidThreshold");
+            }
+        });
+    });
+
+    // ------- vote bitmaps -------
+    describe("vote bitmaps", () => {
+        let proposalPda: PublicKey;
+
+        it("rejects votes on proposals from an older signer set", async () => {
+            const id = await fetchProposalCount();
+            [proposalPda] = getProposalPda(vaultPda, id);
+            await program.methods
+                .createProposal(
+                    recipient.publicKey,
+                    new BN(1),
+                    { sol: {} },
+                    "Before signer change",
+                    null,
+                    null,
+                    false,
+                )
+                .accounts({
+                    proposer: signer1.publicKey,
+                    vaultConfig: vaultPda,
+                    proposal: proposalPda,
+                    systemProgram: SystemProgram.programId,
+                })
+                .rpc();
+
+            const extra = Keypair.generate();
+            await executeGovernance({ addSigner: { signer: extra.publicKey } });
+            await executeGovernance({
+                removeSigner: { signer: extra.publicKey },
+            });
+
+            try {
+                await program.methods
+                    .approveProposal()
+                    .accounts({
+                        signer: signer2.publicKey,
+                        vaultConfig: vaultPda,
+                        proposal: proposalPda,
+                    })
+                    .signers([signer2])
+                    .rpc();
+                expect.fail("should have failed");
+            } catch (err) {
+                expect(err.error.errorCode.code).to.equal("StaleProposal");
+            }
+        });
+
+        it("lets anyone finalize a stale proposal", async () => {
+            await program.methods
+                .finalizeProposal()
+                .accounts({
+                    vaultConfig: vaultPda,
+                    proposal: proposalPda,
+                })
+                .rpc();
+
+            const prop = await program.account.proposal.fetch(proposalPda);
+            expect(prop.status).to.deep.equal({ rejected: {} });
+        });
+    });
+
+    // ------- off-chain memos -------
+    describe("off-chain memos", () => {
+        it("stores only a memo hash and URI", async () => {
+            const id = await fetchProposalCount();
+            const [proposalPda] = getProposalPda(vaultPda, id);
+            const memo = {
+                hash: Array(32).fill(7),
+                uri: "ipfs://bafy-invoice-42",
+            };
+
+            await program.methods
+                .createProposal(
+                    recipient.publicKey,
+                    new BN(1),
+                    { sol: {} },
+                    "",
+                    null,
+                    memo,
+                    false,
+                )
+                .accounts({
+                    proposer: signer1.publicKey,
+                    vaultConfig: vaultPda,
+                    proposal: proposalPda,
+                    systemProgram: SystemProgram.programId,
+                })
+                .rpc();
+
+            const prop = await program.account.proposal.fetch(proposalPda);
+            expect(prop.description).to.equal("");
+            expect(prop.memo.hash).to.deep.equal(memo.hash);
+            expect(prop.memo.uri).to.equal(memo.uri);
+
+            const [firstProposal] = getProposalPda(vaultPda, 0);
+            const memoInfo = await connection.getAccountInfo(proposalPda);
+            const inlineInfo = await connection.getAccountInfo(firstProposal);
+            // Only the actual description and URI bytes are allocated
+            expect(memoInfo.data.length).to.equal(
+                inlineInfo.data.length -
+                    "Pay contributor".length +
+                    memo.uri.length,
+            );
+        });
+    });
+
+    // ------- abstain / quorum -------
+    describe("abstain and quorum", () => {
+        async function vote(
+            method: "approveProposal" | "abstainProposal",
+            signer: Keypair,
+            proposal: PublicKey,
+        ) {
+            await program.methods[method]()
+                .accounts({
+                    signer: signer.publicKey,
+                    vaultConfig: vaultPda,
+                    proposal,
+                })
+                .signers([signer])
+                .rpc();
+        }
+
+        async function createSol(): Promise<PublicKey> {
+            const id = await fetchProposalCount();
+            const [proposalPda] = getProposalPda(vaultPda, id);
+            await program.methods
+                .createProposal(
+                    recipient.publicKey,
+                    new BN(1),
+                    { sol: {} },
+                    "Quorum test",
+                    null,
+                    null,
+                    false,
+                )
+                .accounts({
+                    proposer: signer1.publicKey,
+                    vaultConfig: vaultPda,
+                    proposal: proposalPda,
+                    systemProgram: SystemProgram.programId,
+                })
+                .rpc();
+            return proposalPda;
+        }
+
+        before(async () => {
+            await executeGovernance({ setQuorum: { quorum: 3 } });
+        });
+
+        it("stays active until quorum is met", async () => {
+            const proposalPda = await createSol();
+            await vote("approveProposal", signer2, proposalPda);
+
+            let prop = await program.account.proposal.fetch(proposalPda);
+            expect(prop.status).to.deep.equal({ active: {} });
+
+            await vote("abstainProposal", signer3, proposalPda);
+
+            prop = await program.account.proposal.fetch(proposalPda);
+            expect(
+                decodeVotes(prop.abstainBitmap, signersList),
+            ).to.have.lengthOf(1);
+            expect(prop.status).to.deep.equal({ approved: {} });
+        });
+
+        after(async () => {
+            const id = await fetchProposalCount();
+            const [proposalPda] = getProposalPda(vaultPda, id);
+            await program.methods
+                .createProposal(
+                    vaultPda,
+                    new BN(0),
+                    { governance: { action: { setQuorum: { quorum: 0 } } } },
+                    "Governance",
+                    null,
+                    null,
+                    false,
+                )
+                .accounts({
+                    proposer: signer1.publicKey,
+                    vaultConfig: vaultPda,
+                    proposal: proposalPda,
+                    systemProgram: SystemProgram.programId,
+                })
+                .rpc();
+            await vote("approveProposal", signer2, proposalPda);
+            await vote("abstainProposal", signer3, proposalPda);
+            await program.methods
+                .executeGovernanceProposal()
+                .accounts({
+                    executor: signer1.publicKey,
+                    vaultConfig: vaultPda,
+                    proposal: proposalPda,
+                    assetRegistry: null,
+                })
+                .rpc();
+        });
+    });
+
+    // ------- voting deadline -------
+    describe("finalize_proposal", () => {
+        let proposalPda: PublicKey;
+        let governancePda: PublicKey;
+
+        function finalize(proposal = proposalPda) {
+            return program.methods
+                .finalizeProposal()
+                .accounts({ vaultConfig: vaultPda, proposal })
+                .rpc();
+        }
+
+        // Creates a proposal that signer3 abstains on, so the vote has
+        // quorum 2 but only the proposer's approval.
+        async function createAbstained(
+            recipientKey: PublicKey,
+            transferType: object,
+        ): Promise<PublicKey> {
+            const id = await fetchProposalCount();
+            const [pda] = getProposalPda(vaultPda, id);
+            await program.methods
+                .createProposal(
+                    recipientKey,
+                    new BN(1),
+                    transferType,
+                    "Deadline test",
+                    null,
+                    null,
+                    false,
+                )
+                .accounts({
+                    proposer: signer1.publicKey,
+                    vaultConfig: vaultPda,
+                    proposal: pda,
+                    systemProgram: SystemProgram.programId,
+                })
+                .rpc();
+            await program.methods
+                .abstainProposal()
+                .accounts({
+                    signer: signer3.publicKey,
+                    vaultConfig: vaultPda,
+                    proposal: pda,
+                })
+                .signers([signer3])
+                .rpc();
+            return pda;
+        }
+
+        before(async () => {
+            try {
+                await executeGovernance({
+                    setVotingPeriod: {
+                        votingPeriodSecs: new BN(2),
+                        deadlineRule: { simpleMajority: {} },
+                    },
+                });
+                expect.fail("should have failed");
+            } catch (err) {
+                expect(err.error.errorCode.code).to.equal("InvalidQuorum");
+            }
+
+            await executeGovernance({ setQuorum: { quorum: 2 } });
+            await executeGovernance({
+                setVotingPeriod: {
+                    votingPeriodSecs: new BN(2),
+                    deadlineRule: { simpleMajority: {} },
+                },
+            });
+
+            proposalPda = await createAbstained(recipient.publicKey, {
+                sol: {},
+            });
+            governancePda = await createAbstained(vaultPda, {
+                governance: { action: { setQuorum: { quorum: 1 } } },
+            });
+        });
+
+        it("rejects voting periods above the cap", async () => {
+            try {
+                await executeGovernance({
+                    setVotingPeriod: {
+                        votingPeriodSecs: new BN(366 * 24 * 60 * 60),
+                        deadlineRule: { simpleMajority: {} },
+                    },
+                });
+                expect.fail("should have failed");
+            } catch (err) {
+                expect(err.error.errorCode.code).to.equal(
+                    "InvalidVotingPeriod",
+                );
+            }
+        });
+
+        it("cannot finalize before the deadline", async () => {
+            try {
+                await finalize();
+                expect.fail("should have failed");
+            } catch (err) {
+                expect(err.error.errorCode.code).to.equal("VotingStillOpen");
+            }
+        });
+
+        it("approves by simple majority after the deadline", async () => {
+            await new Promise((resolve) => setTimeout(resolve, 3000));
+            await finalize();
+
+            const prop = await program.account.proposal.fetch(proposalPda);
+            expect(prop.status).to.deep.equal({ approved: {} });
+        });
+
+        it("never passes governance proposals by majority", async () => {
+            await finalize(governancePda);
+
+            const prop = await program.account.proposal.fetch(governancePda);
+            expect(prop.status).to.deep.equal({ rejected: {} });
+        });
+
+        after(async () => {
+            await executeGovernance({
+                setVotingPeriod: {
+                    votingPeriodSecs: new BN(0),
+                    deadlineRule: { reject: {} },
+                },
+            });
+            await executeGovernance({ setQuorum: { quorum: 0 } });
+        });
+    });
+
+    // ------- amend_proposal -------
+    describe("amend_proposal", () => {
+        let proposalPda: PublicKey;
+
+        function amend(amount: number) {
+            return program.methods
+                .amendProposal(
+                    recipient.publicKey,
+                    new BN(amount),
+                    "Pay contributor (corrected)",
+                    null,
+                    null,
+                )
+                .accounts({
+                    proposer: signer1.publicKey,
+                    vaultConfig: vaultPda,
+                    proposal: proposalPda,
+                    systemProgram: SystemProgram.programId,
+                })
+                .rpc();
+        }
+
+        before(async () => {
+            const id = await fetchProposalCount();
+            [proposalPda] = getProposalPda(vaultPda, id);
+            await program.methods
+                .createProposal(
+                    recipient.publicKey,
+                    new BN(10),
+                    { sol: {} },
+                    "Pay contributor",
+                    null,
+                    null,
+                    false,
+                )
+                .accounts({
+                    proposer: signer1.publicKey,
+                    vaultConfig: vaultPda,
+                    proposal: proposalPda,
+                    systemProgram: SystemProgram.programId,
+                })
+                .rpc();
+        });
+
+        it("lets the proposer fix a proposal before others vote", async () => {
+            await amend(100);
+
+            const prop = await program.account.proposal.fetch(proposalPda);
+            expect(prop.amount.toNumber()).to.equal(100);
+            expect(prop.description).to.equal("Pay contributor (corrected)");
+            expect(prop.amendmentCount).to.equal(1);
+            expect(prop.approvalBitmap.isZero()).to.be.true;
+        });
+
+        it("rejects amendments once another signer has voted", async () => {
+            await program.methods
+                .rejectProposal()
+                .accounts({
+                    signer: signer2.publicKey,
+                    vaultConfig: vaultPda,
+                    proposal: proposalPda,
+                })
+                .signers([signer2])
+                .rpc();
+
+            try {
+                await amend(200);
+                expect.fail("should have failed");
+            } catch (err) {
+                expect(err.error.errorCode.code).to.equal("ProposalHasVotes");
+            }
+        });
+    });
+
+    // ------- vote_cancel_proposal -------
+    describe("vote_cancel_proposal", () => {
+        let proposalPda: PublicKey;
+
+        async function voteCancel(signer: Keypair) {
+            await program.methods
+                .voteCancelProposal()
+                .accounts({
+                    signer: signer.publicKey,
+                    vaultConfig: vaultPda,
+                    proposal: proposalPda,
+                })
+                .signers([signer])
+                .rpc();
+        }
+
+        before(async () => {
+            const id = await fetchProposalCount();
+            [proposalPda] = getProposalPda(vaultPda, id);
+            await program.methods
+                .createProposal(
+                    recipient.publicKey,
+                    new BN(1),
+                    { sol: {} },
+                    "Unwanted payment",
+                    null,
+                    null,
+                    false,
+                )
+                .accounts({
+                    proposer: signer1.publicKey,
+                    vaultConfig: vaultPda,
+                    proposal: proposalPda,
+                    systemProgram: SystemProgram.programId,
+                })
+                .rpc();
+            await program.methods
+                .approveProposal()
+                .accounts({
+                    signer: signer2.publicKey,
+                    vaultConfig: vaultPda,
+                    proposal: proposalPda,
+                })
+                .signers([signer2])
+                .rpc();
+        });
+
+        it("cancels an approved proposal at the cancel threshold", async () => {
+            await voteCancel(signer2);
+            let prop = await program.account.proposal.fetch(proposalPda);
+            expect(prop.status).to.deep.equal({ approved: {} });
+
+            await voteCancel(signer3);
+            prop = await program.account.proposal.fetch(proposalPda);
+            expect(prop.status).to.deep.equal({ cancelled: {} });
+        });
+    });
+
+    // ------- partial execution -------
+    describe("partial execution", () => {
+        let proposalPda: PublicKey;
+
+        async function availableLamports(): Promise<number> {
+            const info = await connection.getAccountInfo(vaultPda);
+            const rent = await connection.getMinimumBalanceForRentExemption(
+                info.data.length,
+            );
+            return info.lamports - rent;
+        }
+
+        function execute() {
+            return program.methods
+                .executeSolProposal()
+                .accounts({
+                    executor: signer1.publicKey,
+                    vaultConfig: vaultPda,
+                    proposal: proposalPda,
+                    recipient: recipient.publicKey,
+                    priceUpdate: null,
+                    systemProgram: SystemProgram.programId,
+                    policyProgram: null,
+                })
+                .rpc();
+        }
+
+        before(async () => {
+            const amount = (await availableLamports()) + LAMPORTS_PER_SOL;
+            const id = await fetchProposalCount();
+            [proposalPda] = getProposalPda(vaultPda, id);
+
+            await program.methods
+                .createProposal(
+                    recipient.publicKey,
+                    new BN(amount),
+                    { sol: {} },
+                    "Pay in installments",
+                    null,
+                    null,
+                    true,
+                )
+                .accounts({
+                    proposer: signer1.publicKey,
+                    vaultConfig: vaultPda,
+                    proposal: proposalPda,
+                    systemProgram: SystemProgram.programId,
+                })
+                .rpc();
+            for (const s of [signer2, signer3]) {
+                const prop = await program.account.proposal.fetch(proposalPda);
+                if (!("active" in prop.status)) break;
+                await program.methods
+                    .approveProposal()
+                    .accounts({
+                        signer: s.publicKey,
+                        vaultConfig: vaultPda,
+                        proposal: proposalPda,
+                    })
+                    .signers([s])
+                    .rpc();
+            }
+        });
+
+        it("pays what is available and stays partially executed", async () => {
+            const available = await availableLamports();
+            await execute();
+
+            const prop = await program.account.proposal.fetch(proposalPda);
+            expect(prop.status).to.deep.equal({ partiallyExecuted: {} });
+            expect(prop.amountPaid.toNumber()).to.equal(available);
+        });
+
+        it("pays the remainder once the vault is funded", async () => {
+            await program.methods
+                .depositSol(new BN(2 * LAMPORTS_PER_SOL))
+                .accounts({
+                    depositor: signer1.publicKey,
+                    vaultConfig: vaultPda,
+                    depositRecord: getDepositRecordPda(
+                        vaultPda,
+                        signer1.publicKey,
+                        PublicKey.default,
+                    )[0],
+                    systemProgram: SystemProgram.programId,
+                })
+                .rpc();
+            await execute();
+
+            const prop = await program.account.proposal.fetch(proposalPda);
+            expect(prop.status).to.deep.equal({ executed: {} });
+            expect(prop.amountPaid.eq(prop.amount)).to.be.true;
+        });
+    });
+
+    // ------- execution hooks -------
+    describe("execution hooks", () => {
+        const mockPolicy = anchor.workspace.mockPolicy as Program<MockPolicy>;
+        const policyProgram = mockPolicy.programId;
+        let proposalPda: PublicKey;
+
+        async function createApprovedSol(amount: number): Promise<PublicKey> {
+            const id = await fetchProposalCount();
+            const [pda] = getProposalPda(vaultPda, id);
+
+            await program.methods
+                .createProposal(
+                    recipient.publicKey,
+                    new BN(amount),
+                    { sol: {} },
+                    "Hooked payment",
+                    null,
+                    null,
+                    false,
+                )
+                .accounts({
+                    proposer: signer1.publicKey,
+                    vaultConfig: vaultPda,
+                    proposal: pda,
+                    systemProgram: SystemProgram.programId,
+                })
+                .rpc();
+            for (const s of [signer2, signer3]) {
+                const prop = await program.account.proposal.fetch(pda);
+                if (!("active" in prop.status)) break;
+                await program.methods
+                    .approveProposal()
+                    .accounts({
+                        signer: s.publicKey,
+                        vaultConfig: vaultPda,
+                        proposal: pda,
+                    })
+                    .signers([s])
+                    .rpc();
+            }
+            return pda;
+        }
+
+        function executeSol(proposal: PublicKey, policy: PublicKey | null) {
+            return program.methods
+                .executeSolProposal()
+                .accounts({
+                    executor: signer1.publicKey,
+                    vaultConfig: vaultPda,
+                    proposal,
+                    recipient: recipient.publicKey,
+                    priceUpdate: null,
+                    systemProgram: SystemProgram.programId,
+                    policyProgram: policy,
+                })
+                .rpc();
+        }
+
+        before(async () => {
+            proposalPda = await createApprovedSol(1_000);
+        });
+
+        it("registers a policy program through governance", async () => {
+            await executeGovernance({
+                setPolicyProgram: { program: policyProgram },
+            });
+
+            const vault = await program.account.vaultConfig.fetch(vaultPda);
+            expect(vault.policyProgram.equals(policyProgram)).to.be.true;
+        });
+
+        it("refuses to execute without the registered policy program", async () => {
+            try {
+                await executeSol(proposalPda, null);
+                expect.fail("should have required the policy program");
+            } catch (err) {
+                expect(err.error.errorCode.code).to.equal(
+                    "PolicyProgramMismatch",
+                );
+            }
+
+            const prop = await program.account.proposal.fetch(proposalPda);
+            expect(prop.status).to.deep.equal({ approved: {} });
+        });
+
+        it("executes when the policy check passes", async () => {
+            // The mock's post-execution check also fails unless the proposal
+            // was written back as executed before the CPI.
+            await executeSol(proposalPda, policyProgram);
+
+            const prop = await program.account.proposal.fetch(proposalPda);
+            expect(prop.status).to.deep.equal({ executed: {} });
+        });
+
+        it("aborts execution when the policy check fails", async () => {
+            await program.methods
+                .depositSol(new BN(LAMPORTS_PER_SOL))
+                .accounts({
+                    depositor: signer1.publicKey,
+                    vaultConfig: vaultPda,
+                    depositRecord: getDepositRecordPda(
+                        vaultPda,
+                        signer1.publicKey,
+                        PublicKey.default,
+                    )[0],
+                    systemProgram: SystemProgram.programId,
+                })
+                .rpc();
+            // Above the mock's 0.5 SOL limit
+            const largePda = await createApprovedSol(0.6 * LAMPORTS_PER_SOL);
+            const before = await connection.getBalance(recipient.publicKey);
+
+            try {
+                await executeSol(largePda, policyProgram);
+                expect.fail("should have been blocked by the policy");
+            } catch (err) {
+                expect(err.error.errorCode.code).to.equal("AmountTooLarge");
+            }
+
+            const prop = await program.account.proposal.fetch(largePda);
+            expect(prop.status).to.deep.equal({ approved: {} });
+            expect(await connection.getBalance(recipient.publicKey)).to.equal(
+                before,
+            );
+        });
+
+        it("clears the policy program", async () => {
+            await executeGovernance({ setPolicyProgram: { program: null } });
+
+            const vault = await program.account.vaultConfig.fetch(vaultPda);
+            expect(vault.policyProgram).to.be.null;
+        });
+    });
+
+    // ------- NFT and Core asset proposals -------
+    describe("NFT and Core asset proposals", () => {
+        const MPL_CORE_PROGRAM_ID = new PublicKey(
+            "CoREENxT6tW1HoK8ypY1SxRMZTcVPm7R94rH4PZNhX7d",
+        );
+        const TOKEN_METADATA_PROGRAM_ID = new PublicKey(
+            "metaqbxxUerdq28cj1RbAWkYQm3ybzjb6a8bt518x1s",
+        );
+
+        function borshString(value: string): Buffer {
+            const bytes = Buffer.from(value);
+            const len = Buffer.alloc(4);
+            len.writeUInt32LE(bytes.length);
+            return Buffer.concat([len, bytes]);
+        }
+
+        // Raw Core CreateV1: no collection, no plugins, payer as authority.
+        async function createCoreAsset(owner: PublicKey): Promise<PublicKey> {
+            const asset = Keypair.generate();
+            const none = {
+                pubkey: MPL_CORE_PROGRAM_ID,
+                isSigner: false,
+                isWritable: false,
+            };
+            const ix = new TransactionInstruction({
+                programId: MPL_CORE_PROGRAM_ID,
+                keys: [
+                    {
+                        pubkey: asset.publicKey,
+                        isSigner: true,
+                        isWritable: true,
+                    },
+                    none, // collection
+                    none, // authority
+                    {
+                        pubkey: wallet.publicKey,
+                        isSigner: true,
+                        isWritable: true,
+                    },
+                    { pubkey: owner, isSigner: false, isWritable: false },
+                    none, // update authority
+                    {
+                        pubkey: SystemProgram.programId,
+                        isSigner: false,
+                        isWritable: false,
+                    },
+                    none, // log wrapper
+                ],
+                data: Buffer.concat([
+                    Buffer.from([0, 0]), // CreateV1, DataState::AccountState
+                    borshString("Vault asset"),
+                    borshString("https://example.com/asset.json"),
+                    Buffer.from([0]), // no plugins
+                ]),
+            });
+            await provider.sendAndConfirm(new Transaction().add(ix), [asset]);
+            return asset.publicKey;
+        }
+
+        function getMetadataPdas(mint: PublicKey): [PublicKey, PublicKey] {
+            const seeds = [
+                Buffer.from("metadata"),
+                TOKEN_METADATA_PROGRAM_ID.toBuffer(),
+                mint.toBuffer(),
+            ];
+            const [metadata] = PublicKey.findProgramAddressSync(
+                seeds,
+                TOKEN_METADATA_PROGRAM_ID,
+            );
+            const [edition] = PublicKey.findProgramAddressSync(
+                [...seeds, Buffer.from("edition")],
+                TOKEN_METADATA_PROGRAM_ID,
+            );
+            return [metadata, edition];
+        }
+
+        // Standard NFT held by `owner`: a 0-decimal mint with one token,
+        // then raw Token Metadata CreateMetadataAccountV3 and
+        // CreateMasterEditionV3, which take over the mint authority.
+        async function createStandardNft(owner: PublicKey): Promise<PublicKey> {
+            const mint = await createMint(
+                connection,
+                wallet.payer,
+                wallet.publicKey,
+                wallet.publicKey,
+                0,
+            );
+            const ownerAta = await createAssociatedTokenAccount(
+                connection,
+                wallet.payer,
+                mint,
+                owner,
+                undefined,
+                undefined,
+                undefined,
+                true,
+            );
+            await mintTo(connection, wallet.payer, mint, ownerAta, signer1, 1);
+
+            const [metadata, edition] = getMetadataPdas(mint);
+            const account = (
+                pubkey: PublicKey,
+                isSigner: boolean,
+                isWritable: boolean,
+            ) => ({ pubkey, isSigner, isWritable });
+            const payer = account(wallet.publicKey, true, true);
+            const createMetadata = new TransactionInstruction({
+                programId: TOKEN_METADATA_PROGRAM_ID,
+                keys: [
+                    account(metadata, false, true),
+                    account(mint, false, false),
+                    payer, // mint authority
+                    payer,
+                    payer, // update authority
+                    account(SystemProgram.programId, false, false),
+                    account(SYSVAR_RENT_PUBKEY, false, false),
+                ],
+                data: Buffer.concat([
+                    Buffer.from([33]), // CreateMetadataAccountV3
+                    borshString("Vault NFT"),
+                    borshString("VNFT"),
+                    borshString("https://example.com/nft.json"),
+                    Buffer.from([0, 0]), // seller fee basis points
+                    Buffer.from([0, 0, 0]), // no creators, collection, uses
+                    Buffer.from([1]), // is_mutable
+                    Buffer.from([0]), // no collection details
+                ]),
+            });
+            const createMasterEdition = new TransactionInstruction({
+                programId: TOKEN_METADATA_PROGRAM_ID,
+                keys: [
+                    account(edition, false, true),
+                    account(mint, false, true),
+                    payer, // update authority
+                    payer, // mint authority
+                    payer,
+                    account(metadata, false, true),
+                    account(TOKEN_PROGRAM_ID, false, false),
+                    account(SystemProgram.programId, false, false),
+                    account(SYSVAR_RENT_PUBKEY, false, false),
+                ],
+                data: Buffer.concat([
+                    Buffer.from([17]), // CreateMasterEditionV3
+                    Buffer.from([1]), // Some(max_supply)
+                    new BN(0).toArrayLike(Buffer, "le", 8),
+                ]),
+            });
+            await provider.sendAndConfirm(
+                new Transaction().add(createMetadata, createMasterEdition),
+            );
+            return mint;
+        }
+
+        async function createAssetProposal(
+            transferType: object,
+            amount: number,
+        ): Promise<PublicKey> {
+            const id = await fetchProposalCount();
+            const [proposalPda] = getProposalPda(vaultPda, id);
+            await program.methods
+                .createProposal(
+                    recipient.publicKey,
+                    new BN(amount),
+                    transferType,
+                    "Asset transfer",
+                    null,
+                    null,
+                    false,
+                )
+                .accounts({
+                    proposer: signer1.publicKey,
+                    vaultConfig: vaultPda,
+                    proposal: proposalPda,
+                    systemProgram: SystemProgram.programId,
+                })
+                .rpc();
+            return proposalPda;
+        }
+
+        it("rejects NFT proposals for more than one asset", async () => {
+            try {
+                await createAssetProposal(
+                    { nft: { mint: Keypair.generate().publicKey } },
+                    1_000_000,
+                );
+                expect.fail("should have rejected the amount");
+            } catch (err) {
+                expect(err.error.errorCode.code).to.equal("InvalidAmount");
+            }
+        });
+
+        it("rejects Core asset proposals with a zero amount", async () => {
+            try {
+                await createAssetProposal(
+                    { coreAsset: { asset: Keypair.generate().publicKey } },
+                    0,
+                );
+                expect.fail("should have rejected the amount");
+            } catch (err) {
+                expect(err.error.errorCode.code).to.equal("InvalidAmount");
+            }
+        });
+
+        it("transfers a vault-owned NFT with TransferV1", async () => {
+            const mint = await createStandardNft(vaultPda);
+            const [metadata, edition] = getMetadataPdas(mint);
+            const vaultAta = getAssociatedTokenAddressSync(
+                mint,
+                vaultPda,
+                true,
+            );
+            const recipientAta = getAssociatedTokenAddressSync(
+                mint,
+                recipient.publicKey,
+            );
+            const proposalPda = await createAssetProposal({ nft: { mint } }, 1);
+            await program.methods
+                .approveProposal()
+                .accounts({
+                    signer: signer2.publicKey,
+                    vaultConfig: vaultPda,
+                    proposal: proposalPda,
+                })
+                .signers([signer2])
+                .rpc();
+
+            await program.methods
+                .executeNftProposal()
+                .accounts({
+                    executor: signer1.publicKey,
+                    vaultConfig: vaultPda,
+                    proposal: proposalPda,
+                    recipient: recipient.publicKey,
+                    mint,
+                    vaultTokenAccount: vaultAta,
+                    recipientAta,
+                    metadata,
+                    edition,
+                    tokenRecord: null,
+                    destinationTokenRecord: null,
+                    authorizationRules: null,
+                    authorizationRulesProgram: null,
+                    sysvarInstructions: SYSVAR_INSTRUCTIONS_PUBKEY,
+                    tokenMetadataProgram: TOKEN_METADATA_PROGRAM_ID,
+                    tokenProgram: TOKEN_PROGRAM_ID,
+                    associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+                    systemProgram: SystemProgram.programId,
+                    priceUpdate: null,
+                    policyProgram: null,
+                })
+                .rpc();
+
+            expect(
+                Number((await getAccount(connection, recipientAta)).amount),
+            ).to.equal(1);
+            expect(
+                Number((await getAccount(connection, vaultAta)).amount),
+            ).to.equal(0);
+
+            const prop = await program.account.proposal.fetch(proposalPda);
+            expect(prop.status).to.deep.equal({ executed: {} });
+        });
+
+        it("transfers a vault-owned Core asset to the recipient", async () => {
+            const asset = await createCoreAsset(vaultPda);
+            const proposalPda = await createAssetProposal(
+                { coreAsset: { asset } },
+                1,
+            );
+            await program.methods
+                .approveProposal()
+                .accounts({
+                    signer: signer2.publicKey,
+                    vaultConfig: vaultPda,
+                    proposal: proposalPda,
+                })
+                .signers([signer2])
+                .rpc();
+
+            await program.methods
+                .executeCoreAssetProposal()
+                .accounts({
+                    executor: signer1.publicKey,
+                    vaultConfig: vaultPda,
+                    proposal: proposalPda,
+                    recipient: recipient.publicKey,
+                    asset,
+                    collection: null,
+                    mplCoreProgram: MPL_CORE_PROGRAM_ID,
+                    systemProgram: SystemProgram.programId,
+                    priceUpdate: null,
+                    policyProgram: null,
+                })
+                .rpc();
+
+            // AssetV1 layout: key (u8), then owner
+            const info = await connection.getAccountInfo(asset);
+            const owner = new PublicKey(info.data.subarray(1, 33));
+            expect(owner.equals(recipient.publicKey)).to.be.true;
+
+            const prop = await program.account.proposal.fetch(proposalPda);
+            expect(prop.status).to.deep.equal({ executed: {} });
+        });
+
+        it("refuses to execute a Core proposal with another asset", async () => {
+            const asset = await createCoreAsset(vaultPda);
+            const other = await createCoreAsset(vaultPda);
+            const proposalPda = await createAssetProposal(
+                { coreAsset: { asset } },
+                1,
+            );
+            await program.methods
+                .approveProposal()
+                .accounts({
+                    signer: signer2.publicKey,
+                    vaultConfig: vaultPda,
+                    proposal: proposalPda,
+                })
+                .signers([signer2])
+                .rpc();
+
+            try {
+                await program.methods
+                    .executeCoreAssetProposal()
+                    .accounts({
+                        executor: signer1.publicKey,
+                        vaultConfig: vaultPda,
+                        proposal: proposalPda,
+                        recipient: recipient.publicKey,
+                        asset: other,
+                        collection: null,
+                        mplCoreProgram: MPL_CORE_PROGRAM_ID,
+                        systemProgram: SystemProgram.programId,
+                        priceUpdate: null,
+                        policyProgram: null,
+                    })
+                    .rpc();
+                expect.fail("should have rejected the asset");
+            } catch (err) {
+                expect(err.error.errorCode.code).to.equal(
+                    "InvalidTransferType",
+                );
+            }
+        });
+    });
 });